@@ -31,11 +31,11 @@ use commit_verify::merkle::{MerkleLeaves, MerkleNode};
 use commit_verify::{CommitEncode, CommitStrategy, CommitmentId, Conceal};
 use strict_encoding::{StrictDumb, StrictEncode, StrictWriter};
 
-use super::ExposedState;
+use super::{ConfidentialState, ExposedState};
 use crate::contract::seal::GenesisSeal;
 use crate::{
     AssignmentType, ExposedSeal, GraphSeal, RevealedAttach, RevealedData, RevealedValue,
-    SecretSeal, StateType, VoidState, LIB_NAME_RGB,
+    SecretSeal, StateCommitment, StateType, VoidState, LIB_NAME_RGB,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Display, Error)]
@@ -166,6 +166,20 @@ impl<State: ExposedState, Seal: ExposedSeal> Assign<State, Seal> {
         }
     }
 
+    /// Returns commitment to the assigned state, which is contributed by the
+    /// assignment into the operation commitment. The commitment is the same
+    /// for both revealed and concealed forms of the state.
+    pub fn state_commitment(&self) -> StateCommitment {
+        match self {
+            Assign::Revealed { state, .. } | Assign::ConfidentialSeal { state, .. } => {
+                state.state_commitment()
+            }
+            Assign::Confidential { state, .. } | Assign::ConfidentialState { state, .. } => {
+                state.state_commitment()
+            }
+        }
+    }
+
     pub fn as_revealed_state(&self) -> Option<&State> {
         match self {
             Assign::Revealed { state, .. } | Assign::ConfidentialSeal { state, .. } => Some(state),
@@ -364,6 +378,17 @@ impl<Seal: ExposedSeal> TypedAssigns<Seal> {
         }
     }
 
+    /// Returns commitments to the state of all assignments, in the order of
+    /// the assignments.
+    pub fn state_commitments(&self) -> Vec<StateCommitment> {
+        match self {
+            TypedAssigns::Declarative(set) => set.iter().map(Assign::state_commitment).collect(),
+            TypedAssigns::Fungible(set) => set.iter().map(Assign::state_commitment).collect(),
+            TypedAssigns::Structured(set) => set.iter().map(Assign::state_commitment).collect(),
+            TypedAssigns::Attachment(set) => set.iter().map(Assign::state_commitment).collect(),
+        }
+    }
+
     #[inline]
     pub fn is_declarative(&self) -> bool { matches!(self, TypedAssigns::Declarative(_)) }
 
@@ -685,3 +710,55 @@ impl AssignmentsRef<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bp::secp256k1::rand::thread_rng;
+    use commit_verify::{CommitVerify, Conceal};
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::{AttachId, ConcealedValue, MediaType, PedersenCommitment, RangeProof};
+
+    #[test]
+    fn state_commitment_conceal() {
+        let seal = GraphSeal::strict_dumb();
+        let attach = RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any);
+        let revealed = AssignAttach::revealed(seal, attach.clone());
+        let confidential = AssignAttach::ConfidentialState {
+            seal,
+            state: attach.conceal(),
+        };
+        assert_eq!(revealed.state_commitment(), confidential.state_commitment());
+        assert_eq!(revealed.state_commitment(), revealed.conceal().state_commitment());
+        assert_eq!(revealed.state_commitment(), StateCommitment::Attachment(attach.conceal()));
+    }
+
+    #[test]
+    fn state_commitment_fungible() {
+        let seal = GraphSeal::strict_dumb();
+        let value = RevealedValue::new(5u64, &mut thread_rng());
+        let revealed = AssignFungible::revealed(seal, value);
+        let confidential_seal = AssignFungible::ConfidentialSeal {
+            seal: seal.conceal(),
+            state: value,
+        };
+        let confidential = AssignFungible::ConfidentialState {
+            seal,
+            state: ConcealedValue {
+                commitment: PedersenCommitment::commit(&value),
+                range_proof: RangeProof::default(),
+            },
+        };
+
+        // Range proofs are not committed to, thus only the commitment-encoded
+        // data, contributed into the operation id, must match
+        let encode = |assign: &AssignFungible<GraphSeal>| {
+            let mut data = vec![];
+            assign.state_commitment().commit_encode(&mut data);
+            data
+        };
+        assert_eq!(encode(&revealed), encode(&confidential));
+        assert_eq!(encode(&confidential_seal), encode(&confidential));
+    }
+}
@@ -20,13 +20,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
 use std::str::FromStr;
 
 use amplify::{Bytes32, RawArray};
 use baid58::{Baid58ParseError, FromBaid58, ToBaid58};
 use bp::secp256k1::rand::{thread_rng, RngCore};
-use commit_verify::{CommitVerify, Conceal, StrictEncodedProtocol};
-use strict_encoding::StrictEncode;
+use commit_verify::{CommitVerify, CommitmentProtocol, Conceal, DigestExt, Sha256};
+use strict_encoding::{
+    DecodeError, StrictDecode, StrictDumb, StrictEncode, StrictType, StrictWriter, TypeName,
+    TypedRead, TypedWrite,
+};
 
 use super::{ConfidentialState, ExposedState};
 use crate::{MediaType, StateCommitment, StateData, StateType, LIB_NAME_RGB};
@@ -55,8 +59,110 @@ impl ToBaid58<32> for AttachId {
 impl FromBaid58<32> for AttachId {}
 
 impl FromStr for AttachId {
-    type Err = Baid58ParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::from_baid58_str(s) }
+    type Err = AttachIdParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_baid58_str(s).map_err(AttachIdParseError::from)
+    }
+}
+
+/// Errors parsing [`AttachId`] from a string.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AttachIdParseError {
+    /// attachment id has a wrong prefix '{found}' while '{expected}' is
+    /// required; probably the id was copied from a different context.
+    WrongHri {
+        expected: &'static str,
+        found: String,
+    },
+
+    /// attachment id checksum doesn't match the id value; probably the id was
+    /// mistyped or corrupted.
+    ChecksumMismatch,
+
+    /// attachment id has invalid length; {0}.
+    InvalidLength(Baid58ParseError),
+
+    /// invalid attachment id; {0}.
+    Invalid(Baid58ParseError),
+}
+
+impl From<Baid58ParseError> for AttachIdParseError {
+    fn from(err: Baid58ParseError) -> Self {
+        match err {
+            Baid58ParseError::InvalidHri { expected, found } => {
+                AttachIdParseError::WrongHri { expected, found }
+            }
+            Baid58ParseError::ChecksumMismatch { .. } |
+            Baid58ParseError::InvalidChecksumLen(_) |
+            Baid58ParseError::InvalidMnemonic(_) => AttachIdParseError::ChecksumMismatch,
+            Baid58ParseError::InvalidLen { .. } |
+            Baid58ParseError::ValueTooShort(_) |
+            Baid58ParseError::NonValueTooLong(_) |
+            Baid58ParseError::InvalidBase58Length => AttachIdParseError::InvalidLength(err),
+            err => AttachIdParseError::Invalid(err),
+        }
+    }
+}
+
+/// Salt used in [`RevealedAttach`] to blind the attachment commitment.
+///
+/// The type intentionally has no [`Default`] implementation: a salt must be
+/// either generated with [`Salt::random`] or explicitly provided by the
+/// caller, preventing accidental reuse of the same (zero) salt across
+/// different attachments.
+///
+/// The salt is strict-encoded and serialized exactly like the underlying
+/// `u64` value.
+#[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, From)]
+#[wrapper(Deref)]
+#[display(inner)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct Salt(#[from] u64);
+
+impl Salt {
+    /// Generates new random salt using `thread_rng`.
+    pub fn random() -> Self { Salt::with_rng(&mut thread_rng()) }
+
+    /// Generates new random salt using the provided random number generator.
+    pub fn with_rng<R: RngCore>(rng: &mut R) -> Self { Salt(rng.next_u64()) }
+}
+
+impl StrictDumb for Salt {
+    fn strict_dumb() -> Self { Salt(u64::strict_dumb()) }
+}
+impl StrictType for Salt {
+    const STRICT_LIB_NAME: &'static str = u64::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { u64::strict_name() }
+}
+impl StrictEncode for Salt {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.0.strict_encode(writer)
+    }
+}
+impl StrictDecode for Salt {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        u64::strict_decode(reader).map(Salt)
+    }
+}
+
+/// Metadata-only view of an attachment, containing its id and media type but
+/// not the salt used for concealing [`RevealedAttach`].
+#[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct AttachmentHeader {
+    pub id: AttachId,
+    pub media_type: MediaType,
 }
 
 #[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
@@ -74,21 +180,70 @@ pub struct RevealedAttach {
     /// We do not enforce a MIME standard since non-standard types can be also
     /// used
     pub media_type: MediaType,
-    pub salt: u64,
+    pub salt: Salt,
 }
 
 impl RevealedAttach {
     /// Creates new revealed attachment for the attachment id and MIME type.
-    /// Uses `thread_rng` to initialize [`RevealedAttach::salt`].
+    /// Uses [`Salt::random`] to initialize [`RevealedAttach::salt`].
     pub fn new(id: AttachId, media_type: MediaType) -> Self {
+        Self::with_salt(id, media_type, Salt::random())
+    }
+
+    /// Creates new revealed attachment for the attachment id and the
+    /// [`MediaType::canonical`] form of the MIME type, such that logically
+    /// equal media types differing only in case or whitespace produce the
+    /// same attachment. Uses [`Salt::random`] to initialize
+    /// [`RevealedAttach::salt`].
+    ///
+    /// Use [`RevealedAttach::new`] to keep an intentionally nonstandard media
+    /// type as is.
+    pub fn new_canonical(id: AttachId, media_type: &MediaType) -> Self {
+        Self::new(id, media_type.canonical())
+    }
+
+    /// Creates new revealed attachment for the attachment id and MIME type,
+    /// generating the salt with the provided random number generator.
+    pub fn new_with_rng<R: RngCore>(id: AttachId, media_type: MediaType, rng: &mut R) -> Self {
+        Self::with_salt(id, media_type, Salt::with_rng(rng))
+    }
+
+    /// Creates new revealed attachment for the attachment id and MIME type
+    /// using explicitly provided salt.
+    pub fn with_salt(id: AttachId, media_type: MediaType, salt: Salt) -> Self {
         Self {
             id,
             media_type,
-            salt: thread_rng().next_u64(),
+            salt,
+        }
+    }
+
+    /// Returns the same attachment with a new salt generated with the provided
+    /// random number generator, such that it conceals into a different
+    /// [`ConcealedAttach`]. The new salt is always distinct from the current
+    /// one.
+    pub fn reroll_salt<R: RngCore>(&self, rng: &mut R) -> RevealedAttach {
+        let mut salt = Salt::with_rng(rng);
+        while salt == self.salt {
+            salt = Salt::with_rng(rng);
+        }
+        Self::with_salt(self.id, self.media_type.clone(), salt)
+    }
+
+    /// Returns attachment metadata without the salt, which may be disclosed
+    /// to a party deciding whether to download the attachment data.
+    pub fn header(&self) -> AttachmentHeader {
+        AttachmentHeader {
+            id: self.id,
+            media_type: self.media_type.clone(),
         }
     }
 }
 
+impl From<&RevealedAttach> for AttachmentHeader {
+    fn from(attach: &RevealedAttach) -> Self { attach.header() }
+}
+
 impl ExposedState for RevealedAttach {
     type Confidential = ConcealedAttach;
     fn state_type(&self) -> StateType { StateType::Attachment }
@@ -121,11 +276,337 @@ pub struct ConcealedAttach(
     Bytes32,
 );
 
+impl ConcealedAttach {
+    /// Checks that the concealed attachment is a commitment to the `revealed`
+    /// one, i.e. that the revealed data disclose this concealed state.
+    pub fn verify_reveal(&self, revealed: &RevealedAttach) -> bool {
+        *self == ConcealedAttach::commit(revealed)
+    }
+}
+
 impl ConfidentialState for ConcealedAttach {
     fn state_type(&self) -> StateType { StateType::Attachment }
     fn state_commitment(&self) -> StateCommitment { StateCommitment::Attachment(*self) }
 }
 
-impl CommitVerify<RevealedAttach, StrictEncodedProtocol> for ConcealedAttach {
-    fn commit(revealed: &RevealedAttach) -> Self { Bytes32::commit(revealed).into() }
+/// Tag used by [`AttachmentProtocol`] for committing to attachments.
+pub const ATTACHMENT_COMMITMENT_TAG: [u8; 32] = *b"urn:lnpbp:rgb:attachment:v1#2310";
+
+/// Commitment protocol for concealing attachments: tagged SHA256 hash of the
+/// strict-encoded [`RevealedAttach`], using [`ATTACHMENT_COMMITMENT_TAG`].
+///
+/// The dedicated tag separates attachment commitments from other 32-byte
+/// hashes used in RGB.
+pub struct AttachmentProtocol;
+
+impl CommitmentProtocol for AttachmentProtocol {}
+
+impl CommitVerify<RevealedAttach, AttachmentProtocol> for ConcealedAttach {
+    fn commit(revealed: &RevealedAttach) -> Self {
+        let mut engine = Sha256::from_tag(ATTACHMENT_COMMITMENT_TAG);
+        let writer = StrictWriter::with(u32::MAX as usize, &mut engine);
+        revealed
+            .strict_encode(writer)
+            .expect("hashers do not error");
+        ConcealedAttach::from(engine.finish())
+    }
+}
+
+/// Magic bytes of the media types which can be detected by [`sniff_matches`].
+const MEDIA_TYPE_MAGIC: [(MediaType, &[u8]); 5] = [
+    (MediaType::ImagePng, b"\x89PNG\r\n\x1a\n"),
+    (MediaType::ImageJpeg, b"\xFF\xD8\xFF"),
+    (MediaType::ImageGif, b"GIF87a"),
+    (MediaType::ImageGif, b"GIF89a"),
+    (MediaType::ApplicationPdf, b"%PDF-"),
+];
+
+/// Checks that the attachment `data` look like the `declared` media type,
+/// using magic bytes for the common types (PNG, JPEG, GIF and PDF).
+///
+/// Always returns `true` for media types which can't be detected this way,
+/// including [`MediaType::Any`]. The check is a safety helper for the
+/// software rendering attachments and is not a part of the consensus
+/// validation.
+pub fn sniff_matches(data: &[u8], declared: &MediaType) -> bool {
+    let declared = declared.canonical().essence();
+    let mut magics = MEDIA_TYPE_MAGIC
+        .iter()
+        .filter(|(ty, _)| ty.essence() == declared)
+        .peekable();
+    magics.peek().is_none() || magics.any(|(_, magic)| data.starts_with(magic))
+}
+
+/// Media types of executables and scripts, which are reported by
+/// [`is_potentially_dangerous`].
+pub const DANGEROUS_MEDIA_TYPES: [&str; 14] = [
+    "application/x-msdownload",
+    "application/x-msdos-program",
+    "application/x-msi",
+    "application/vnd.microsoft.portable-executable",
+    "application/x-executable",
+    "application/x-elf",
+    "application/x-mach-binary",
+    "application/java-archive",
+    "application/x-sh",
+    "application/x-shellscript",
+    "application/x-bat",
+    "application/x-powershell",
+    "application/javascript",
+    "text/javascript",
+];
+
+/// Detects whether the media type is one of the [`DANGEROUS_MEDIA_TYPES`],
+/// such that wallets should warn users before opening an attachment of this
+/// type.
+///
+/// The check is advisory and is not a part of the consensus validation.
+pub fn is_potentially_dangerous(media_type: &MediaType) -> bool {
+    DANGEROUS_MEDIA_TYPES.contains(&media_type.canonical().essence().as_str())
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::ToHex;
+    use bp::secp256k1::rand::rngs::StdRng;
+    use bp::secp256k1::rand::SeedableRng;
+    use strict_encoding::{StrictReader, StrictWriter};
+
+    use super::*;
+    use crate::NoiseDumb;
+
+    #[test]
+    fn attach_id_parse() {
+        let id = AttachId::from_raw_array(*b"EV4350-'4vwj'4;v-w94w'e'vFVVDhpq");
+        let baid58 = id.to_baid58();
+        assert_eq!(AttachId::from_str(&id.to_string()), Ok(id));
+
+        let full = format!("{baid58::<}");
+        assert!(full.starts_with("att:"));
+        assert_eq!(AttachId::from_str(&full), Ok(id));
+
+        let wrong_hri = full.replacen("att:", "rgb:", 1);
+        assert_eq!(
+            AttachId::from_str(&wrong_hri),
+            Err(AttachIdParseError::WrongHri {
+                expected: "att",
+                found: s!("rgb"),
+            })
+        );
+
+        let other = AttachId::from_raw_array([0xAB; 32]).to_baid58();
+        let wrong_checksum = format!("{other::<}").replacen(&other.to_string(), &id.to_string(), 1);
+        assert_eq!(AttachId::from_str(&wrong_checksum), Err(AttachIdParseError::ChecksumMismatch));
+
+        assert!(matches!(
+            AttachId::from_str(&id.to_string()[..30]),
+            Err(AttachIdParseError::InvalidLength(_))
+        ));
+    }
+
+    #[test]
+    fn salt_wire_compat() {
+        let salt = Salt::from(0x0123_4567_89AB_CDEF);
+        let salt_data = salt
+            .strict_encode(StrictWriter::in_memory(8))
+            .unwrap()
+            .unbox();
+        let u64_data = 0x0123_4567_89AB_CDEFu64
+            .strict_encode(StrictWriter::in_memory(8))
+            .unwrap()
+            .unbox();
+        assert_eq!(salt_data, u64_data);
+        assert_eq!(Salt::strict_name(), u64::strict_name());
+
+        let attach =
+            RevealedAttach::with_salt(AttachId::from_raw_array([0x11; 32]), MediaType::Any, salt);
+        assert_eq!(attach.salt, salt);
+        assert_eq!(*attach.salt, 0x0123_4567_89AB_CDEF);
+    }
+
+    #[test]
+    fn seeded_attachments() {
+        let id = AttachId::from_raw_array([0x11; 32]);
+        let attach1 =
+            RevealedAttach::new_with_rng(id, MediaType::Any, &mut StdRng::seed_from_u64(1));
+        let attach2 =
+            RevealedAttach::new_with_rng(id, MediaType::Any, &mut StdRng::seed_from_u64(1));
+        let attach3 =
+            RevealedAttach::new_with_rng(id, MediaType::Any, &mut StdRng::seed_from_u64(2));
+        assert_eq!(attach1, attach2);
+        assert_eq!(attach1.conceal(), attach2.conceal());
+        assert_ne!(attach1.salt, attach3.salt);
+
+        let noise1 = NoiseDumb::with_rng(&mut StdRng::seed_from_u64(1));
+        let noise2 = NoiseDumb::with_rng(&mut StdRng::seed_from_u64(1));
+        assert_eq!(noise1, noise2);
+        assert_ne!(noise1, NoiseDumb::with_rng(&mut StdRng::seed_from_u64(2)));
+    }
+
+    #[test]
+    fn concealed_attach_vector() {
+        let attach = RevealedAttach::with_salt(
+            AttachId::from_raw_array([0x11; 32]),
+            MediaType::Any,
+            Salt::from(5),
+        );
+        assert_eq!(
+            attach.conceal().to_hex(),
+            "79ce86b82c82833bdd048122ed4e6a356be431ff847f241518f769b320688f6b"
+        );
+        assert_ne!(attach.conceal(), ConcealedAttach::from(Bytes32::commit(&attach)));
+    }
+
+    #[test]
+    fn verify_reveal() {
+        let id = AttachId::from_raw_array([0x11; 32]);
+        let attach = RevealedAttach::with_salt(id, MediaType::Any, Salt::from(1));
+        let concealed = attach.conceal();
+        assert!(concealed.verify_reveal(&attach));
+
+        let other_salt = RevealedAttach::with_salt(id, MediaType::Any, Salt::from(2));
+        assert!(!concealed.verify_reveal(&other_salt));
+        let other_id = RevealedAttach::with_salt(
+            AttachId::from_raw_array([0x12; 32]),
+            MediaType::Any,
+            1.into(),
+        );
+        assert!(!concealed.verify_reveal(&other_id));
+    }
+
+    #[test]
+    fn canonical_media_type() {
+        let id = AttachId::from_raw_array([0x11; 32]);
+        let raw = MediaType::raw("Image", " PNG").unwrap();
+
+        let canonical = RevealedAttach::new_canonical(id, &raw);
+        assert_eq!(canonical.media_type, MediaType::ImagePng);
+        let nonstandard =
+            RevealedAttach::new_canonical(id, &MediaType::raw("X-Vendor", "Format").unwrap());
+        assert_eq!(nonstandard.media_type, MediaType::raw("x-vendor", "format").unwrap());
+
+        let preserved = RevealedAttach::with_salt(id, raw.clone(), Salt::from(1));
+        assert_eq!(preserved.media_type, raw);
+        let canonical = RevealedAttach::with_salt(id, raw.canonical(), Salt::from(1));
+        assert_ne!(preserved.conceal(), canonical.conceal());
+        assert_eq!(
+            canonical.conceal(),
+            RevealedAttach::with_salt(id, MediaType::ImagePng, Salt::from(1)).conceal()
+        );
+    }
+
+    #[test]
+    fn media_type_params_commitment() {
+        let id = AttachId::from_raw_array([0x11; 32]);
+        let plain = RevealedAttach::with_salt(id, MediaType::TextPlain, Salt::from(1));
+        let utf8 = MediaType::TextPlain.with_param("charset", "utf-8").unwrap();
+        let utf8 = RevealedAttach::with_salt(id, utf8, Salt::from(1));
+        let latin = MediaType::TextPlain
+            .with_param("charset", "latin1")
+            .unwrap();
+        let latin = RevealedAttach::with_salt(id, latin, Salt::from(1));
+
+        assert_ne!(plain.conceal(), utf8.conceal());
+        assert_ne!(utf8.conceal(), latin.conceal());
+        assert!(utf8.conceal().verify_reveal(&utf8));
+        assert!(!utf8.conceal().verify_reveal(&latin));
+    }
+
+    #[test]
+    fn attachment_header() {
+        let id = AttachId::from_raw_array([0x11; 32]);
+        let attach1 = RevealedAttach::with_salt(id, MediaType::Any, Salt::from(1));
+        let attach2 = RevealedAttach::with_salt(id, MediaType::Any, Salt::from(2));
+        let header = attach1.header();
+        assert_eq!(header, AttachmentHeader {
+            id,
+            media_type: MediaType::Any
+        });
+        assert_eq!(header, AttachmentHeader::from(&attach2));
+
+        let data = header
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        assert_eq!(data.len(), 33);
+        let decoded =
+            AttachmentHeader::strict_decode(&mut StrictReader::in_memory(data, usize::MAX));
+        assert_eq!(decoded.unwrap(), header);
+    }
+
+    #[test]
+    fn sniff_matches() {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR";
+        let jpeg = b"\xFF\xD8\xFF\xE0\x00\x10JFIF";
+        let gif = b"GIF89a\x01\x00\x01\x00";
+        let pdf = b"%PDF-1.7\n";
+
+        assert!(super::sniff_matches(png, &MediaType::ImagePng));
+        assert!(super::sniff_matches(jpeg, &MediaType::ImageJpeg));
+        assert!(super::sniff_matches(gif, &MediaType::ImageGif));
+        assert!(super::sniff_matches(b"GIF87a", &MediaType::ImageGif));
+        assert!(super::sniff_matches(pdf, &MediaType::ApplicationPdf));
+
+        assert!(!super::sniff_matches(jpeg, &MediaType::ImagePng));
+        assert!(!super::sniff_matches(pdf, &MediaType::ImageJpeg));
+        assert!(!super::sniff_matches(png, &MediaType::ImageGif));
+        assert!(!super::sniff_matches(gif, &MediaType::ApplicationPdf));
+        assert!(!super::sniff_matches(b"", &MediaType::ImagePng));
+        assert!(super::sniff_matches(png, &MediaType::raw("Image", "PNG").unwrap()));
+        assert!(!super::sniff_matches(jpeg, &MediaType::raw("Image", "PNG").unwrap()));
+        let png_params = MediaType::ImagePng.with_param("x", "y").unwrap();
+        assert!(!super::sniff_matches(jpeg, &png_params));
+
+        for data in [&png[..], jpeg, gif, pdf, b""] {
+            assert!(super::sniff_matches(data, &MediaType::TextPlain));
+            assert!(super::sniff_matches(data, &MediaType::ApplicationOctetStream));
+            assert!(super::sniff_matches(data, &MediaType::Any));
+        }
+    }
+
+    #[test]
+    fn reroll_salt() {
+        let attach = RevealedAttach::new_with_rng(
+            AttachId::strict_dumb(),
+            MediaType::Any,
+            &mut thread_rng(),
+        );
+        let rerolled = attach.reroll_salt(&mut StdRng::seed_from_u64(1));
+        assert_eq!(rerolled.id, attach.id);
+        assert_eq!(rerolled.media_type, attach.media_type);
+        assert_eq!(rerolled.header(), attach.header());
+        assert_ne!(rerolled.salt, attach.salt);
+        assert_ne!(rerolled.conceal(), attach.conceal());
+
+        let fixed = RevealedAttach::with_salt(attach.id, attach.media_type, rerolled.salt);
+        assert_ne!(fixed.reroll_salt(&mut StdRng::seed_from_u64(1)).salt, fixed.salt);
+    }
+
+    #[test]
+    fn dangerous_media_types() {
+        for media_type in [
+            MediaType::ApplicationXMsdownload,
+            MediaType::ApplicationXSh,
+            MediaType::ApplicationJavascript,
+            MediaType::raw("Application", "X-MSDownload").unwrap(),
+            MediaType::raw("application", "x-msi").unwrap(),
+            MediaType::raw(" text", "JavaScript").unwrap(),
+            MediaType::ApplicationXSh
+                .with_param("charset", "utf-8")
+                .unwrap(),
+        ] {
+            assert!(is_potentially_dangerous(&media_type));
+        }
+        for media_type in [
+            MediaType::TextPlain,
+            MediaType::ImagePng,
+            MediaType::ImageJpeg,
+            MediaType::ImageGif,
+            MediaType::ApplicationOctetStream,
+            MediaType::ApplicationPdf,
+            MediaType::Any,
+        ] {
+            assert!(!is_potentially_dangerous(&media_type));
+        }
+    }
 }
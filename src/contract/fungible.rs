@@ -31,10 +31,11 @@
 //! using elliptic curve homomorphic cryptography such as Pedesen commitments.
 
 use core::cmp::Ordering;
-use core::fmt::Debug;
+use core::fmt::{self, Debug, Display, Formatter};
 use core::num::ParseIntError;
-use core::ops::Deref;
+use core::ops::{Deref, Range};
 use core::str::FromStr;
+use std::collections::HashMap;
 use std::io;
 use std::io::Write;
 
@@ -44,22 +45,33 @@ use amplify::hex::{Error, FromHex, ToHex};
 use amplify::{hex, Array, Bytes32, Wrapper};
 use bp::secp256k1::rand::thread_rng;
 use commit_verify::{
-    CommitEncode, CommitVerify, CommitmentProtocol, Conceal, Digest, Sha256, UntaggedProtocol,
+    CommitEncode, CommitVerify, CommitmentProtocol, Conceal, Digest, DigestExt, Sha256,
+    UntaggedProtocol,
 };
 use secp256k1_zkp::rand::{Rng, RngCore};
 use secp256k1_zkp::SECP256K1;
 use strict_encoding::{
-    DecodeError, ReadTuple, StrictDecode, StrictDumb, StrictEncode, TypedRead, TypedWrite,
-    WriteTuple,
+    DecodeError, ReadStruct, ReadTuple, StrictDecode, StrictDumb, StrictEncode, StrictReader,
+    StrictWriter, TypedRead, TypedWrite, WriteTuple,
 };
+use strict_types::value::StrictNum;
+use strict_types::StrictVal;
+use subtle::ConstantTimeEq;
 
 use super::{ConfidentialState, ExposedState};
-use crate::{schema, StateCommitment, StateData, StateType, LIB_NAME_RGB};
+use crate::{
+    schema, ContractId, StateCommitment, StateData, StateType, LIB_NAME_RGB, RGB_STRICT_VERSION,
+};
 
 /// An atom of an additive state, which thus can be monomorphically encrypted.
+///
+/// NB: Derived [`Ord`] compares variants first and values only within the same
+/// variant. It is suitable for use in ordered collections, but not for
+/// comparing amounts of different widths; use [`FungibleState::cmp_value`]
+/// for that.
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, From)]
 #[display(inner)]
-#[derive(StrictType, StrictEncode, StrictDecode)]
+#[derive(StrictType, StrictEncode)]
 #[strict_type(lib = LIB_NAME_RGB, tags = custom)]
 #[cfg_attr(
     feature = "serde",
@@ -78,6 +90,20 @@ impl Default for FungibleState {
     fn default() -> Self { FungibleState::Bits64(0) }
 }
 
+// The decoding is manual in order to distinguish wider values introduced by
+// future RGB versions from the garbage data.
+impl StrictDecode for FungibleState {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        match u8::strict_decode(reader)? {
+            8 => u64::strict_decode(reader).map(FungibleState::Bits64),
+            tag => Err(DecodeError::DataIntegrityError(format!(
+                "unsupported fungible state encoding {tag} belonging to a future RGB version, \
+                 while this library supports {RGB_STRICT_VERSION}. Please update your software"
+            ))),
+        }
+    }
+}
+
 impl From<RevealedValue> for FungibleState {
     fn from(revealed: RevealedValue) -> Self { revealed.value }
 }
@@ -87,22 +113,286 @@ impl FromStr for FungibleState {
     fn from_str(s: &str) -> Result<Self, Self::Err> { s.parse().map(FungibleState::Bits64) }
 }
 
-impl From<FungibleState> for u64 {
-    fn from(value: FungibleState) -> Self {
+/// Errors converting [`FungibleState`] into a primitive integer type.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum FungibleConversionError {
+    /// fungible state of {0} type has value exceeding the range of the target
+    /// integer type.
+    Overflow(schema::FungibleType),
+
+    /// fungible state of {0} type has negative value, which can't be
+    /// represented by an unsigned integer type.
+    Negative(schema::FungibleType),
+}
+
+impl TryFrom<FungibleState> for u64 {
+    type Error = FungibleConversionError;
+
+    fn try_from(value: FungibleState) -> Result<Self, Self::Error> {
         match value {
-            FungibleState::Bits64(val) => val,
+            FungibleState::Bits64(val) => Ok(val),
         }
     }
 }
 
+/// Errors converting strict-types value into [`FungibleState`].
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum FungibleValError {
+    /// strict value `{0}` is not an integer and can't represent fungible state.
+    NotInteger(String),
+
+    /// integer value {0} is out of the range of the supported fungible state
+    /// types.
+    OutOfRange(String),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+/// invalid length of a big-endian fungible state representation ({0} bytes).
+pub struct FungibleLenError(pub usize);
+
+impl PartialEq<u64> for FungibleState {
+    fn eq(&self, other: &u64) -> bool { self.try_as_u64() == Some(*other) }
+}
+
 impl FungibleState {
+    /// Minimal representable fungible value.
+    pub const ZERO: Self = FungibleState::Bits64(0);
+
+    /// Maximal representable fungible value (for the widest supported state
+    /// variant).
+    pub const MAX: Self = FungibleState::Bits64(u64::MAX);
+
+    /// Formats the value with digits grouped by three, separated by `sep`
+    /// (like `1,000,000` for `','`), independently of the system locale.
+    pub fn grouped(&self, sep: char) -> String {
+        let digits = self.to_string();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+        for (pos, digit) in digits.chars().enumerate() {
+            if pos > 0 && (digits.len() - pos) % 3 == 0 {
+                grouped.push(sep);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// Compares numeric values of two fungible states independently of their
+    /// variants. Returns `None` if the values are not comparable, i.e. they
+    /// have variants of different signedness.
+    pub fn cmp_value(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (FungibleState::Bits64(a), FungibleState::Bits64(b)) => Some(a.cmp(b)),
+        }
+    }
+
     pub fn fungible_type(&self) -> schema::FungibleType {
         match self {
             FungibleState::Bits64(_) => schema::FungibleType::Unsigned64Bit,
         }
     }
 
-    pub fn as_u64(&self) -> u64 { (*self).into() }
+    /// Returns the value as `u64`. Values which can't be represented as an
+    /// unsigned 64-bit integer saturate to `0` (for negative values) or
+    /// `u64::MAX`; use `u64::try_from` to detect these cases.
+    pub fn as_u64(&self) -> u64 {
+        match u64::try_from(*self) {
+            Ok(val) => val,
+            Err(FungibleConversionError::Overflow(_)) => u64::MAX,
+            Err(FungibleConversionError::Negative(_)) => 0,
+        }
+    }
+
+    /// Returns canonical fixed-width big-endian representation of the value,
+    /// independent from strict encoding. The width of the representation
+    /// depends on the state variant (8 bytes for [`FungibleState::Bits64`]).
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        match self {
+            FungibleState::Bits64(val) => val.to_be_bytes().to_vec(),
+        }
+    }
+
+    /// Parses fixed-width big-endian representation of the value, detecting
+    /// the state variant from the byte length.
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<Self, FungibleLenError> {
+        match bytes.len() {
+            8 => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(bytes);
+                Ok(FungibleState::Bits64(u64::from_be_bytes(buf)))
+            }
+            len => Err(FungibleLenError(len)),
+        }
+    }
+
+    /// Converts the state into the strict-types integer value of the primitive
+    /// type matching the state variant (U64 for [`FungibleState::Bits64`]).
+    pub fn to_strict_val(&self) -> StrictVal {
+        match self {
+            FungibleState::Bits64(val) => StrictVal::num(*val),
+        }
+    }
+
+    /// Constructs the state from a strict-types integer value.
+    ///
+    /// # Errors
+    ///
+    /// If the value is not an integer or lies outside of the range of the
+    /// supported fungible state variants.
+    pub fn try_from_strict_val(val: &StrictVal) -> Result<Self, FungibleValError> {
+        let StrictVal::Number(num) = val else {
+            return Err(FungibleValError::NotInteger(val.to_string()));
+        };
+        let bits64 = match num {
+            StrictNum::Uint(n) => u64::try_from(*n).ok(),
+            StrictNum::Int(n) => u64::try_from(*n).ok(),
+            StrictNum::BigUint(n) if n.leading_zeros() >= 1024 - 64 => Some(n.low_u64()),
+            StrictNum::BigInt(n) if n.leading_zeros() >= 1024 - 64 => Some(n.low_u64()),
+            _ => None,
+        };
+        bits64
+            .map(FungibleState::Bits64)
+            .ok_or_else(|| FungibleValError::OutOfRange(val.to_string()))
+    }
+
+    /// Returns the value as `u64` if the state variant is representable as an
+    /// unsigned 64-bit integer, or `None` otherwise.
+    pub fn try_as_u64(&self) -> Option<u64> { u64::try_from(*self).ok() }
+
+    /// Adds two values, saturating at the maximum value of the state type.
+    ///
+    /// NB: The arithmetic must not be used for balancing commitments, which
+    /// requires exact arithmetic over the prime field; it is intended for UI
+    /// and value aggregation purposes only.
+    pub fn saturating_add(self, other: impl Into<FungibleState>) -> Self {
+        FungibleState::Bits64(self.as_u64().saturating_add(other.into().as_u64()))
+    }
+
+    /// Subtracts a value, saturating at zero.
+    ///
+    /// NB: Must not be used for balancing commitments, see
+    /// [`FungibleState::saturating_add`].
+    pub fn saturating_sub(self, other: impl Into<FungibleState>) -> Self {
+        FungibleState::Bits64(self.as_u64().saturating_sub(other.into().as_u64()))
+    }
+
+    /// Adds two values, wrapping around the boundary of the state type.
+    ///
+    /// NB: Must not be used for balancing commitments, see
+    /// [`FungibleState::saturating_add`].
+    pub fn wrapping_add(self, other: impl Into<FungibleState>) -> Self {
+        FungibleState::Bits64(self.as_u64().wrapping_add(other.into().as_u64()))
+    }
+
+    /// Subtracts a value, wrapping around the boundary of the state type.
+    ///
+    /// NB: Must not be used for balancing commitments, see
+    /// [`FungibleState::saturating_add`].
+    pub fn wrapping_sub(self, other: impl Into<FungibleState>) -> Self {
+        FungibleState::Bits64(self.as_u64().wrapping_sub(other.into().as_u64()))
+    }
+}
+
+/// Amount of fungible state in atomic (indivisible) units, as it is stored in
+/// the contract state and committed to.
+///
+/// See [`DisplayAmount`] for the representation of the amount in display
+/// units for a given asset precision.
+#[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default, Display, From)]
+#[wrapper(Deref)]
+#[display(inner)]
+pub struct AtomicAmount(#[from] u64);
+
+impl From<FungibleState> for AtomicAmount {
+    fn from(state: FungibleState) -> Self {
+        let FungibleState::Bits64(value) = state;
+        AtomicAmount(value)
+    }
+}
+
+impl From<AtomicAmount> for FungibleState {
+    fn from(amount: AtomicAmount) -> Self { FungibleState::Bits64(amount.0) }
+}
+
+impl AtomicAmount {
+    /// Represents the amount in display units of an asset with the given
+    /// `precision`, i.e. the number of decimal digits after the point.
+    pub fn to_display(self, precision: u8) -> DisplayAmount {
+        DisplayAmount {
+            units: self.0,
+            precision,
+        }
+    }
+}
+
+/// Decimal amount in display units, equal to `units / 10^precision`; for
+/// instance `1.50` is represented with 150 units and precision 2.
+///
+/// Display amounts are never committed to and must be converted to
+/// [`AtomicAmount`] with [`DisplayAmount::to_atomic`] first.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct DisplayAmount {
+    pub units: u64,
+    pub precision: u8,
+}
+
+impl DisplayAmount {
+    /// Converts the amount into atomic units of an asset with the given
+    /// `precision`.
+    ///
+    /// # Errors
+    ///
+    /// - [`AmountError::PrecisionLoss`] if the amount has more significant
+    ///   decimal digits than supported by the asset precision, such that the
+    ///   conversion would require rounding;
+    /// - [`AmountError::Overflow`] if the amount in atomic units exceeds
+    ///   `u64::MAX`.
+    pub fn to_atomic(self, precision: u8) -> Result<AtomicAmount, AmountError> {
+        let units = self.units as u128;
+        let atomic = if precision >= self.precision {
+            match 10u128.checked_pow((precision - self.precision) as u32) {
+                Some(factor) => units.checked_mul(factor),
+                None if units == 0 => Some(0),
+                None => None,
+            }
+            .ok_or(AmountError::Overflow(self, precision))?
+        } else {
+            match 10u128.checked_pow((self.precision - precision) as u32) {
+                Some(factor) if units % factor == 0 => units / factor,
+                None if units == 0 => 0,
+                _ => return Err(AmountError::PrecisionLoss(self, precision)),
+            }
+        };
+        u64::try_from(atomic)
+            .map(AtomicAmount)
+            .map_err(|_| AmountError::Overflow(self, precision))
+    }
+}
+
+impl Display for DisplayAmount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let digits = self.units.to_string();
+        let precision = self.precision as usize;
+        if precision == 0 {
+            return f.write_str(&digits);
+        }
+        let digits = format!("{digits:0>width$}", width = precision + 1);
+        let (int, frac) = digits.split_at(digits.len() - precision);
+        write!(f, "{int}.{frac}")
+    }
+}
+
+/// Errors converting [`DisplayAmount`] into [`AtomicAmount`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AmountError {
+    /// amount {0} can't be represented with precision {1} without rounding.
+    PrecisionLoss(DisplayAmount, u8),
+
+    /// amount {0} with precision {1} exceeds the maximal atomic amount.
+    Overflow(DisplayAmount, u8),
 }
 
 /// Blinding factor used in creating Pedersen commitment to an [`AtomicValue`].
@@ -169,11 +459,79 @@ impl TryFrom<[u8; 32]> for BlindingFactor {
     }
 }
 
+/// Tag used by [`BlindingFactor::derive`].
+pub const BLINDING_DERIVATION_TAG: [u8; 32] = *b"urn:lnpbp:rgb:blinding:v1#202310";
+
+impl BlindingFactor {
+    /// Deterministically derives blinding factor for the output with a given
+    /// `index` in a `contract` from a wallet `seed`.
+    ///
+    /// The blinding factor is a BIP-340-style tagged SHA256 hash
+    ///
+    /// ```text
+    /// SHA256(SHA256(tag) || SHA256(tag) || seed || contract || index)
+    /// ```
+    ///
+    /// where `tag` is [`BLINDING_DERIVATION_TAG`] and `index` is serialized
+    /// as 4 little-endian bytes. The hash is interpreted as a big-endian
+    /// secp256k1 scalar without reduction.
+    ///
+    /// # Errors
+    ///
+    /// If the hash is zero or exceeds the secp256k1 field order. The
+    /// probability of this is negligible (below 2^-127); wallets should skip
+    /// the index when it happens.
+    pub fn derive(
+        seed: &[u8; 32],
+        contract: [u8; 32],
+        index: u32,
+    ) -> Result<Self, FieldOrderOverflow> {
+        let mut engine = Sha256::from_tag(BLINDING_DERIVATION_TAG);
+        engine.input_raw(seed);
+        engine.input_raw(&contract);
+        engine.input_raw(&index.to_le_bytes());
+        BlindingFactor::try_from(engine.finish())
+    }
+
+    /// Returns publicly known blinding factor (equal to scalar one), which is
+    /// used for fungible state with public amount; see
+    /// [`PedersenCommitment::commit_public`].
+    pub fn public() -> Self {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        BlindingFactor::try_from(one).expect("one is a valid secp256k1 scalar")
+    }
+}
+
+/// Errors re-blinding fungible state with [`RevealedValue::reblind`] or
+/// [`PedersenCommitment::reblind`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ReblindError {
+    /// re-blinding results in a zero blinding factor; a different blinding
+    /// factor must be used.
+    ZeroBlinding,
+
+    /// blinding factor {0} is zero or overflows prime field order for
+    /// Secp256k1 curve.
+    InvalidBlinding(BlindingFactor),
+}
+
+impl BlindingFactor {
+    /// Converts blinding factor into a secp256k1 secret key, failing for
+    /// blinding factors which were constructed from arbitrary data (for
+    /// instance, deserialized) and are not valid scalars.
+    fn to_secret_key(self) -> Result<secp256k1_zkp::SecretKey, ReblindError> {
+        secp256k1_zkp::SecretKey::from_slice(self.0.as_inner())
+            .map_err(|_| ReblindError::InvalidBlinding(self))
+    }
+}
+
 /// State item for a homomorphically-encryptable state.
 ///
 /// Consists of the 64-bit value and
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[derive(StrictType, StrictDumb, StrictEncode)]
 #[strict_type(lib = LIB_NAME_RGB, rename = "RevealedFungible")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(crate = "serde_crate"))]
 pub struct RevealedValue {
@@ -182,31 +540,365 @@ pub struct RevealedValue {
 
     /// Blinding factor used in Pedersen commitment
     pub blinding: BlindingFactor,
+
+    /// Tag of the contract-scoped asset generator used in the Pedersen
+    /// commitment (see [`contract_generator_tag`]), or `None` if the global
+    /// [`asset_generator`] is used.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub generator_tag: Option<Bytes32>,
+}
+
+// Blinding factors which are not valid non-zero secp256k1 scalars are rejected
+// here, so the failure does not surface later when the commitment is created.
+// A zero blinding would also reveal the value from its commitment.
+impl StrictDecode for RevealedValue {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_struct(|r| {
+            let value = r.read_field(fname!("value"))?;
+            let blinding: BlindingFactor = r.read_field(fname!("blinding"))?;
+            secp256k1_zkp::SecretKey::from_slice(blinding.as_slice()).map_err(|_| {
+                DecodeError::DataIntegrityError(s!("invalid or zero blinding factor"))
+            })?;
+            let generator_tag = r.read_field(fname!("generatorTag"))?;
+            Ok(RevealedValue {
+                value,
+                blinding,
+                generator_tag,
+            })
+        })
+    }
 }
 
 impl RevealedValue {
     /// Constructs new state using the provided value and random generator for
     /// creating blinding factor.
-    pub fn new<R: Rng + RngCore>(value: impl Into<FungibleState>, rng: &mut R) -> Self {
+    pub fn new<R: Rng + RngCore>(value: impl Into<AtomicAmount>, rng: &mut R) -> Self {
         Self {
-            value: value.into(),
+            value: FungibleState::from(value.into()),
             blinding: BlindingFactor::from(secp256k1_zkp::SecretKey::new(rng)),
+            generator_tag: None,
         }
     }
 
+    /// Constructs new state bound to the asset generator of the `contract`
+    /// (see [`contract_asset_generator`]), using the provided random
+    /// generator for creating blinding factor.
+    ///
+    /// Commitments to the same value bound to different contracts are
+    /// different and can't be balanced against each other.
+    pub fn new_for_contract<R: Rng + RngCore>(
+        value: impl Into<AtomicAmount>,
+        contract: ContractId,
+        rng: &mut R,
+    ) -> Self {
+        RevealedValue::new(value, rng).bind_to_contract(contract)
+    }
+
     /// Convenience constructor.
     pub fn with(value: impl Into<FungibleState>, blinding: impl Into<BlindingFactor>) -> Self {
         Self {
             value: value.into(),
             blinding: blinding.into(),
+            generator_tag: None,
+        }
+    }
+
+    /// Returns the same value and blinding factor bound to the asset
+    /// generator of the `contract`.
+    pub fn bind_to_contract(self, contract: ContractId) -> Self {
+        RevealedValue {
+            generator_tag: Some(contract_generator_tag(contract).into()),
+            ..self
+        }
+    }
+
+    /// Returns asset generator used in the Pedersen commitment to the value.
+    pub fn generator(&self) -> secp256k1_zkp::Generator {
+        match self.generator_tag {
+            Some(tag) => {
+                let tag = secp256k1_zkp::Tag::from(tag.to_inner());
+                secp256k1_zkp::Generator::new_unblinded(SECP256K1, tag)
+            }
+            None => asset_generator(),
+        }
+    }
+
+    /// Returns fixed-layout representation of the state, independent from
+    /// strict encoding: 8-byte big-endian value followed by the 32-byte
+    /// blinding factor.
+    ///
+    /// The generator tag is not included, so values bound to a contract must
+    /// be re-bound with [`RevealedValue::bind_to_contract`] after parsing.
+    ///
+    /// NB: Strict encoding remains the canonical wire form of the state.
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let FungibleState::Bits64(value) = self.value;
+        let mut bytes = [0u8; 40];
+        bytes[..8].copy_from_slice(&value.to_be_bytes());
+        bytes[8..].copy_from_slice(self.blinding.as_slice());
+        bytes
+    }
+
+    /// Parses fixed-layout representation of the state produced by
+    /// [`RevealedValue::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// If the blinding factor is zero or overflows the secp256k1 field order.
+    pub fn from_bytes(bytes: [u8; 40]) -> Result<Self, FieldOrderOverflow> {
+        let mut value = [0u8; 8];
+        value.copy_from_slice(&bytes[..8]);
+        let mut blinding = [0u8; 32];
+        blinding.copy_from_slice(&bytes[8..]);
+        Ok(RevealedValue::with(u64::from_be_bytes(value), BlindingFactor::try_from(blinding)?))
+    }
+
+    /// Constructs disclosure of the value and its blinding factor for the
+    /// owned state of type `ty`, which can be sent to the payment recipient as
+    /// a receipt.
+    pub fn to_disclosure(&self, ty: schema::AssignmentType) -> Disclosure {
+        Disclosure {
+            assignment_type: ty,
+            value: self.value.as_u64(),
+            blinding: self.blinding,
+            generator_tag: self.generator_tag,
+        }
+    }
+
+    /// Starts construction of the state for the `value` with a blinding
+    /// factor which must be specified with one of the [`RevealedValueBuilder`]
+    /// methods.
+    pub fn builder(value: impl Into<FungibleState>) -> RevealedValueBuilder {
+        RevealedValueBuilder {
+            value: value.into(),
+            blinding: None,
+        }
+    }
+
+    /// Adds `add_blinding` to the blinding factor, keeping the value. The
+    /// commitment to the re-blinded value is the same as the original
+    /// commitment re-blinded with [`PedersenCommitment::reblind`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ReblindError::InvalidBlinding`] if either the current or the added
+    ///   blinding factor is not a valid secp256k1 scalar;
+    /// - [`ReblindError::ZeroBlinding`] if the resulting blinding factor is
+    ///   zero.
+    ///
+    /// In case of an error the value is left unchanged.
+    pub fn reblind(&mut self, add_blinding: &BlindingFactor) -> Result<(), ReblindError> {
+        use secp256k1_zkp::Scalar;
+
+        let blinding = self
+            .blinding
+            .to_secret_key()?
+            .add_tweak(&Scalar::from(add_blinding.to_secret_key()?))
+            .map_err(|_| ReblindError::ZeroBlinding)?;
+        self.blinding = blinding.into();
+        Ok(())
+    }
+
+    /// Merges two revealed values into a single one, with amount equal to the
+    /// sum of the amounts and blinding factor equal to the sum of the
+    /// blinding factors. The commitment to the merged value thus equals to
+    /// the sum of the commitments to the merged values.
+    ///
+    /// # Errors
+    ///
+    /// - [`MergeError::ValueOverflow`] if the sum of the amounts exceeds the
+    ///   value range of the state;
+    /// - [`MergeError::InvalidBlinding`] if a blinding factor of any of the
+    ///   values is not a valid secp256k1 scalar;
+    /// - [`MergeError::ZeroBlinding`] if the blinding factors are opposite and
+    ///   sum up to zero;
+    /// - [`MergeError::GeneratorMismatch`] if the values are committed with
+    ///   different asset generators.
+    pub fn merge(&self, other: &RevealedValue) -> Result<RevealedValue, MergeError> {
+        if self.generator_tag != other.generator_tag {
+            return Err(MergeError::GeneratorMismatch);
+        }
+        let (FungibleState::Bits64(value), FungibleState::Bits64(other_value)) =
+            (self.value, other.value);
+        let value = value
+            .checked_add(other_value)
+            .ok_or(MergeError::ValueOverflow)?;
+        let mut merged = RevealedValue {
+            value: value.into(),
+            ..*self
+        };
+        merged.reblind(&other.blinding).map_err(|err| match err {
+            ReblindError::ZeroBlinding => MergeError::ZeroBlinding,
+            ReblindError::InvalidBlinding(blinding) => MergeError::InvalidBlinding(blinding),
+        })?;
+        Ok(merged)
+    }
+
+    /// Splits input `value` blinded with `input_blinding` into a number of
+    /// revealed values with amounts given by `parts`, such that the sum of
+    /// the Pedersen commitments to the produced values equals to the
+    /// commitment to the input value.
+    ///
+    /// All blinding factors except the last one are random; the last blinding
+    /// factor balances the sum of the blinding factors to the
+    /// `input_blinding`. The produced values use the global asset generator;
+    /// parts of a value bound to a contract must be bound to the same
+    /// contract with [`RevealedValue::bind_to_contract`].
+    ///
+    /// # Errors
+    ///
+    /// - [`SplitError::NoParts`] if `parts` are empty;
+    /// - [`SplitError::SumMismatch`] if `parts` do not sum up to the `value`;
+    /// - [`SplitError::InvalidBlinding`] if `input_blinding` is not a valid
+    ///   secp256k1 scalar;
+    /// - [`SplitError::ZeroBlinding`] if the balancing blinding factor happens
+    ///   to be zero (negligible probability); the split may be retried.
+    pub fn split<R: Rng + RngCore>(
+        value: u64,
+        parts: &[u64],
+        input_blinding: &BlindingFactor,
+        rng: &mut R,
+    ) -> Result<Vec<RevealedValue>, SplitError> {
+        use secp256k1_zkp::{Scalar, SecretKey};
+
+        let Some((last, rest)) = parts.split_last() else {
+            return Err(SplitError::NoParts);
+        };
+        let sum = parts
+            .iter()
+            .try_fold(0u64, |sum, part| sum.checked_add(*part))
+            .ok_or(SplitError::SumMismatch)?;
+        if sum != value {
+            return Err(SplitError::SumMismatch);
         }
+
+        let mut outputs = Vec::with_capacity(parts.len());
+        let mut balance = input_blinding
+            .to_secret_key()
+            .map_err(|_| SplitError::InvalidBlinding(*input_blinding))?;
+        for part in rest {
+            let output = RevealedValue::new(*part, rng);
+            let blinding = SecretKey::from(output.blinding).negate();
+            balance = balance
+                .add_tweak(&Scalar::from(blinding))
+                .map_err(|_| SplitError::ZeroBlinding)?;
+            outputs.push(output);
+        }
+        outputs.push(RevealedValue::with(*last, balance));
+        Ok(outputs)
+    }
+}
+
+/// Errors merging two [`RevealedValue`]s with [`RevealedValue::merge`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum MergeError {
+    /// sum of the merged amounts exceeds the maximal fungible state value.
+    ValueOverflow,
+
+    /// blinding factors of the merged values sum up to zero.
+    ZeroBlinding,
+
+    /// blinding factor {0} of a merged value is zero or overflows prime field
+    /// order for Secp256k1 curve.
+    InvalidBlinding(BlindingFactor),
+
+    /// merged values are committed with different asset generators.
+    GeneratorMismatch,
+}
+
+/// Builder for [`RevealedValue`], created with [`RevealedValue::builder`].
+///
+/// If the blinding factor is set multiple times, the last one is used.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RevealedValueBuilder {
+    value: FungibleState,
+    blinding: Option<Result<BlindingFactor, FieldOrderOverflow>>,
+}
+
+impl RevealedValueBuilder {
+    /// Uses random blinding factor produced by `rng`.
+    pub fn random_blinding<R: Rng + RngCore>(mut self, rng: &mut R) -> Self {
+        self.blinding = Some(Ok(secp256k1_zkp::SecretKey::new(rng).into()));
+        self
     }
+
+    /// Uses blinding factor deterministically derived from the wallet `seed`
+    /// with [`BlindingFactor::derive`].
+    pub fn derived_blinding(mut self, seed: &[u8; 32], contract: [u8; 32], index: u32) -> Self {
+        self.blinding = Some(BlindingFactor::derive(seed, contract, index));
+        self
+    }
+
+    /// Uses explicitly provided blinding factor.
+    pub fn explicit_blinding(mut self, blinding: BlindingFactor) -> Self {
+        self.blinding = Some(
+            secp256k1_zkp::SecretKey::from_slice(blinding.as_slice())
+                .map(|_| blinding)
+                .map_err(|_| FieldOrderOverflow),
+        );
+        self
+    }
+
+    /// Constructs the state.
+    ///
+    /// # Errors
+    ///
+    /// - [`ValueBuildError::NoBlinding`] if no blinding factor was set;
+    /// - [`ValueBuildError::InvalidBlinding`] if the blinding factor is zero or
+    ///   overflows the secp256k1 field order.
+    pub fn build(self) -> Result<RevealedValue, ValueBuildError> {
+        let blinding = self.blinding.ok_or(ValueBuildError::NoBlinding)??;
+        Ok(RevealedValue::with(self.value, blinding))
+    }
+}
+
+/// Errors constructing [`RevealedValue`] with [`RevealedValueBuilder`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum ValueBuildError {
+    /// blinding factor for the revealed value is not provided.
+    NoBlinding,
+
+    /// blinding factor is zero or overflows prime field order for Secp256k1
+    /// curve.
+    #[from(FieldOrderOverflow)]
+    InvalidBlinding,
+}
+
+/// Errors splitting [`RevealedValue`] into multiple values.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum SplitError {
+    /// no parts are given to split the value into.
+    NoParts,
+
+    /// parts do not sum up to the value being split.
+    SumMismatch,
+
+    /// the balancing blinding factor is zero; the split must be retried with
+    /// different random blinding factors.
+    ZeroBlinding,
+
+    /// input blinding factor {0} is zero or overflows prime field order for
+    /// Secp256k1 curve.
+    InvalidBlinding(BlindingFactor),
 }
 
 impl ExposedState for RevealedValue {
     type Confidential = ConcealedValue;
     fn state_type(&self) -> StateType { StateType::Fungible }
     fn state_data(&self) -> StateData { StateData::Fungible(*self) }
+
+    // Range proofs are not a part of the state commitment, so instead of
+    // panicking in `ConcealedValue::commit` we use zero placeholder, keeping the
+    // result deterministic.
+    fn state_commitment(&self) -> StateCommitment {
+        StateCommitment::Fungible(ConcealedValue {
+            commitment: PedersenCommitment::commit(self),
+            range_proof: RangeProof::Placeholder(NoiseDumb([0u8; 512].into())),
+        })
+    }
 }
 
 impl Conceal for RevealedValue {
@@ -228,7 +920,7 @@ impl PartialOrd for RevealedValue {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self.value.partial_cmp(&other.value) {
             None => None,
-            Some(Ordering::Equal) => self.blinding.0.partial_cmp(&other.blinding.0),
+            Some(Ordering::Equal) => Some(self.cmp(other)),
             other => other,
         }
     }
@@ -237,12 +929,62 @@ impl PartialOrd for RevealedValue {
 impl Ord for RevealedValue {
     fn cmp(&self, other: &Self) -> Ordering {
         match self.value.cmp(&other.value) {
-            Ordering::Equal => self.blinding.0.cmp(&other.blinding.0),
+            Ordering::Equal => self
+                .blinding
+                .0
+                .cmp(&other.blinding.0)
+                .then_with(|| self.generator_tag.cmp(&other.generator_tag)),
             other => other,
         }
     }
 }
 
+/// Strict-encodes a list of revealed values into a single blob, framed with
+/// the number of the values encoded as a 32-bit little-endian integer, such
+/// that the list can be restored with [`decode_values`].
+///
+/// # Panics
+///
+/// If there are more than `u32::MAX` values.
+pub fn encode_values(values: &[RevealedValue]) -> Vec<u8> {
+    let count = u32::try_from(values.len()).expect("too many values");
+    let mut writer = count
+        .strict_encode(StrictWriter::in_memory(usize::MAX))
+        .expect("in-memory writer doesn't fail");
+    for value in values {
+        writer = value
+            .strict_encode(writer)
+            .expect("in-memory writer doesn't fail");
+    }
+    writer.unbox()
+}
+
+/// Decodes list of revealed values produced by [`encode_values`].
+///
+/// # Errors
+///
+/// If the data are not a valid encoding of the values, or if the data
+/// contain more bytes than needed for the number of values they declare.
+pub fn decode_values(bytes: &[u8]) -> Result<Vec<RevealedValue>, DecodeError> {
+    use std::io::BufRead;
+
+    let mut reader = StrictReader::with(bytes.len(), io::Cursor::new(bytes));
+    let count = u32::strict_decode(&mut reader)?;
+    let mut values = Vec::new();
+    for _ in 0..count {
+        values.push(RevealedValue::strict_decode(&mut reader)?);
+    }
+    if !reader.unbox().fill_buf()?.is_empty() {
+        return Err(DecodeError::DataIntegrityError(s!(
+            "data contain more than the declared number of values"
+        )));
+    }
+    Ok(values)
+}
+
+/// Length of the compressed serialization of [`PedersenCommitment`] in bytes.
+pub const PEDERSEN_COMMITMENT_LEN: usize = 33;
+
 /// Opaque type holding pedersen commitment for an [`FungibleState`].
 #[derive(Wrapper, Copy, Clone, Eq, PartialEq, Hash, Debug, From)]
 #[wrapper(Deref, FromStr, Display, LowerHex)]
@@ -259,7 +1001,7 @@ pub struct PedersenCommitment(secp256k1_zkp::PedersenCommitment);
 
 impl StrictDumb for PedersenCommitment {
     fn strict_dumb() -> Self {
-        secp256k1_zkp::PedersenCommitment::from_slice(&[0x08; 33])
+        secp256k1_zkp::PedersenCommitment::from_slice(&[0x08; PEDERSEN_COMMITMENT_LEN])
             .expect("hardcoded pedersen commitment value")
             .into()
     }
@@ -267,14 +1009,14 @@ impl StrictDumb for PedersenCommitment {
 
 impl StrictEncode for PedersenCommitment {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
-        writer.write_tuple::<Self>(|w| Ok(w.write_field(&self.0.serialize())?.complete()))
+        writer.write_tuple::<Self>(|w| Ok(w.write_field(&self.to_bytes())?.complete()))
     }
 }
 
 impl StrictDecode for PedersenCommitment {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
         reader.read_tuple(|r| {
-            let commitment = r.read_field::<[u8; 33]>()?;
+            let commitment = r.read_field::<[u8; PEDERSEN_COMMITMENT_LEN]>()?;
             secp256k1_zkp::PedersenCommitment::from_slice(&commitment)
                 .map_err(|_| {
                     DecodeError::DataIntegrityError(s!("invalid pedersen commitment data"))
@@ -284,69 +1026,397 @@ impl StrictDecode for PedersenCommitment {
     }
 }
 
-impl CommitVerify<RevealedValue, UntaggedProtocol> for PedersenCommitment {
-    fn commit(revealed: &RevealedValue) -> Self {
-        use secp256k1_zkp::{Generator, Tag, Tweak};
+/// Returns tag used for constructing asset generator for Pedersen commitments
+/// to [`FungibleState`].
+///
+/// The tag is computed as a SHA256 hash of the uncompressed serialization of
+/// the secp256k1 curve generator point `G`.
+pub fn asset_generator_tag() -> [u8; 32] {
+    // TODO: Check that we create correct generator value.
+    let one_key = secp256k1_zkp::SecretKey::from_slice(&secp256k1_zkp::constants::ONE)
+        .expect("secret key from a constant");
+    let g = secp256k1_zkp::PublicKey::from_secret_key(SECP256K1, &one_key);
+    Sha256::digest(&g.serialize_uncompressed()).into()
+}
 
-        let blinding = Tweak::from_inner(revealed.blinding.0.into_inner())
-            .expect("type guarantees of BlindingFactor are broken");
-        let FungibleState::Bits64(value) = revealed.value;
+/// Returns asset generator used in Pedersen commitments to [`FungibleState`].
+/// The generator is unblinded and is constructed from the
+/// [`asset_generator_tag`].
+pub fn asset_generator() -> secp256k1_zkp::Generator {
+    let tag = secp256k1_zkp::Tag::from(asset_generator_tag());
+    secp256k1_zkp::Generator::new_unblinded(SECP256K1, tag)
+}
 
-        // TODO: Check that we create correct generator value.
-        let one_key = secp256k1_zkp::SecretKey::from_slice(&secp256k1_zkp::constants::ONE)
-            .expect("secret key from a constant");
-        let g = secp256k1_zkp::PublicKey::from_secret_key(SECP256K1, &one_key);
-        let h: [u8; 32] = Sha256::digest(&g.serialize_uncompressed()).into();
-        let tag = Tag::from(h);
-        let generator = Generator::new_unblinded(SECP256K1, tag);
+/// Tag used by [`contract_generator_tag`] for deriving contract-scoped asset
+/// generators.
+pub const CONTRACT_GENERATOR_TAG: [u8; 32] = *b"urn:lnpbp:rgb:generators:v1#2310";
 
-        secp256k1_zkp::PedersenCommitment::new(SECP256K1, value, blinding, generator).into()
-    }
+/// Returns tag for constructing asset generator scoped to a specific
+/// `contract`, computed as a tagged SHA256 hash of [`asset_generator_tag`]
+/// followed by the contract id, using [`CONTRACT_GENERATOR_TAG`].
+pub fn contract_generator_tag(contract: ContractId) -> [u8; 32] {
+    let mut engine = Sha256::from_tag(CONTRACT_GENERATOR_TAG);
+    engine.input_raw(&asset_generator_tag());
+    engine.input_raw(contract.as_slice());
+    engine.finish()
 }
 
-/// A dumb placeholder for a future bulletproofs.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-#[derive(StrictType, StrictEncode, StrictDecode)]
-#[strict_type(lib = LIB_NAME_RGB)]
-#[cfg_attr(
-    feature = "serde",
-    derive(Serialize, Deserialize),
-    serde(crate = "serde_crate", transparent)
-)]
-pub struct NoiseDumb(Array<u8, 512>);
+/// Returns unblinded asset generator scoped to a specific `contract`,
+/// constructed from the [`contract_generator_tag`].
+pub fn contract_asset_generator(contract: ContractId) -> secp256k1_zkp::Generator {
+    let tag = secp256k1_zkp::Tag::from(contract_generator_tag(contract));
+    secp256k1_zkp::Generator::new_unblinded(SECP256K1, tag)
+}
 
-impl Default for NoiseDumb {
-    fn default() -> Self {
-        let mut dumb = [0u8; 512];
-        thread_rng().fill(&mut dumb);
-        NoiseDumb(dumb.into())
+impl PedersenCommitment {
+    /// Returns compressed serialization of the commitment point.
+    pub fn to_bytes(&self) -> [u8; PEDERSEN_COMMITMENT_LEN] { self.0.serialize() }
+
+    /// Verifies that the sum of the `inputs` commitments is equal to the sum of
+    /// the `outputs` commitments.
+    pub fn verify_sum(inputs: &[PedersenCommitment], outputs: &[PedersenCommitment]) -> bool {
+        let inputs = inputs.iter().map(|c| c.0).collect::<Vec<_>>();
+        let outputs = outputs.iter().map(|c| c.0).collect::<Vec<_>>();
+        secp256k1_zkp::verify_commitments_sum_to_equal(SECP256K1, &inputs, &outputs)
     }
-}
 
-/// Range proof value.
-///
-/// Range proofs must be used alongside [`PedersenCommitment`]s to ensure that
-/// the value do not overflow on arithmetic operations with the commitments.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-#[derive(StrictType, StrictEncode, StrictDecode)]
-#[strict_type(lib = LIB_NAME_RGB, tags = custom)]
-#[cfg_attr(
-    feature = "serde",
-    derive(Serialize, Deserialize),
-    serde(crate = "serde_crate", rename_all = "camelCase")
-)]
-pub enum RangeProof {
-    /// Value used when bulletproofs library is not available.
+    /// Computes the homomorphic sum of the `commitments`, which opens with the
+    /// sum of the committed values and the sum of their blinding factors.
     ///
-    /// Always fails validation if no source value is given.
-    #[strict_type(tag = 0xFF)]
-    Placeholder(NoiseDumb),
-}
+    /// The sum allows to check a claimed total of confidential amounts
+    /// without revealing the individual values.
+    ///
+    /// Returns `None` if `commitments` are empty or sum up to the point at
+    /// infinity, which happens only if both the values and the blinding
+    /// factors cancel each other out.
+    pub fn sum(commitments: &[PedersenCommitment]) -> Option<PedersenCommitment> {
+        use secp256k1_zkp::PublicKey;
+
+        let points = commitments
+            .iter()
+            .map(|commitment| {
+                PublicKey::from_slice(&commitment.to_uncompressed())
+                    .expect("commitment is always a valid curve point")
+            })
+            .collect::<Vec<_>>();
+        let sum = PublicKey::combine_keys(&points.iter().collect::<Vec<_>>()).ok()?;
+        let inputs = commitments.iter().map(|c| c.0).collect::<Vec<_>>();
+        // Compressed commitments encode the quadratic residuosity of the y
+        // coordinate rather than its parity, so we pick the flag matching the
+        // sum.
+        [0x08u8, 0x09].into_iter().find_map(|flag| {
+            let mut data = sum.serialize();
+            data[0] = flag;
+            let candidate = secp256k1_zkp::PedersenCommitment::from_slice(&data)
+                .expect("public key x coordinate is always on the curve");
+            secp256k1_zkp::verify_commitments_sum_to_equal(SECP256K1, &inputs, &[candidate])
+                .then_some(PedersenCommitment::from(candidate))
+        })
+    }
+
+    /// Verifies a two-level tree of commitments: each of the `subtotals` must
+    /// be equal to the sum of the `leaves` in its range, and the sum of the
+    /// subtotals must be equal to the `total`.
+    ///
+    /// The tree allows to check balance of large operations incrementally or
+    /// in parallel, with each subtotal verified independently.
+    ///
+    /// Returns `false` if the subtotal ranges are empty, overlap, are out of
+    /// bounds or do not cover all the leaves.
+    pub fn verify_tree(
+        leaves: &[PedersenCommitment],
+        subtotals: &[(Range<usize>, PedersenCommitment)],
+        total: PedersenCommitment,
+    ) -> bool {
+        let mut ranges = subtotals
+            .iter()
+            .map(|(range, _)| range.clone())
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|range| range.start);
+        let mut covered = 0;
+        for range in ranges {
+            if range.is_empty() || range.start != covered {
+                return false;
+            }
+            covered = range.end;
+        }
+        if covered != leaves.len() || leaves.is_empty() {
+            return false;
+        }
+
+        let subtotals_valid = subtotals.iter().all(|(range, subtotal)| {
+            PedersenCommitment::verify_sum(&leaves[range.clone()], &[*subtotal])
+        });
+        let subtotals = subtotals
+            .iter()
+            .map(|(_, subtotal)| *subtotal)
+            .collect::<Vec<_>>();
+        subtotals_valid && PedersenCommitment::verify_sum(&subtotals, &[total])
+    }
+
+    /// Commits to a public `value` using [`BlindingFactor::public`], such
+    /// that the commitment has the same format as the commitments to the
+    /// confidential amounts and can be balanced against them.
+    ///
+    /// NB: The value is trivially recoverable from the commitment by trying
+    /// candidate amounts, so the commitment must be used only when the amount
+    /// is intended to be public. Mixing public and confidential outputs also
+    /// reduces the anonymity set of the confidential ones, since the public
+    /// amounts are excluded from the possible values.
+    pub fn commit_public(value: u64) -> Self {
+        PedersenCommitment::commit(&RevealedValue::with(value, BlindingFactor::public()))
+    }
+
+    /// Re-randomizes the commitment by adding `add_blinding` to its blinding
+    /// factor, keeping the committed value unchanged. This is equivalent to
+    /// adding a commitment to zero with the `add_blinding` blinding factor.
+    ///
+    /// The new commitment opens with the original [`RevealedValue`] re-blinded
+    /// with the same factor using [`RevealedValue::reblind`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ReblindError::InvalidBlinding`] if `add_blinding` is not a valid
+    ///   secp256k1 scalar;
+    /// - [`ReblindError::ZeroBlinding`] if the resulting commitment is a point
+    ///   at infinity, which happens only for a zero value committed with a
+    ///   blinding factor opposite to the `add_blinding`.
+    pub fn reblind(&self, add_blinding: &BlindingFactor) -> Result<Self, ReblindError> {
+        use secp256k1_zkp::{PublicKey, Scalar, Tweak};
+
+        let key = add_blinding.to_secret_key()?;
+        let tweak = Tweak::from_inner(key.secret_bytes()).expect("secret key is a valid tweak");
+        let zero = secp256k1_zkp::PedersenCommitment::new(SECP256K1, 0, tweak, asset_generator());
+        let scalar = Scalar::from(key);
+
+        // Commitments are serialized with the quadratic residuosity of the y
+        // coordinate instead of its parity, so we have to try both points with
+        // the same x coordinate and pick the sum matching `self + zero`.
+        for parity in [0x02u8, 0x03] {
+            let mut key = self.to_bytes();
+            key[0] = parity;
+            let Ok(sum) = PublicKey::from_slice(&key)
+                .expect("commitment x coordinate is always on the curve")
+                .add_exp_tweak(SECP256K1, &scalar)
+            else {
+                continue;
+            };
+            for flag in [0x08u8, 0x09] {
+                let mut data = sum.serialize();
+                data[0] = flag;
+                let candidate = secp256k1_zkp::PedersenCommitment::from_slice(&data)
+                    .expect("public key x coordinate is always on the curve");
+                if secp256k1_zkp::verify_commitments_sum_to_equal(SECP256K1, &[self.0, zero], &[
+                    candidate,
+                ]) {
+                    return Ok(candidate.into());
+                }
+            }
+        }
+        Err(ReblindError::ZeroBlinding)
+    }
+}
+
+impl PedersenCommitment {
+    /// Returns the commitment as an uncompressed secp256k1 point (`0x04`
+    /// prefix followed by x and y coordinates), for interoperability with
+    /// verifiers not supporting the compressed form.
+    ///
+    /// NB: Only the compressed 33-byte form is used on the wire and in
+    /// commitments; the uncompressed form is just an alternative
+    /// representation.
+    pub fn to_uncompressed(&self) -> [u8; 65] {
+        use secp256k1_zkp::{PublicKey, Scalar, SecretKey};
+
+        // The compressed form indicates the quadratic residuosity of the y
+        // coordinate, not its parity. To find out which of the two points with
+        // the same x coordinate is the commitment, we shift the commitment by
+        // a generator multiple and compare the result with both shifted
+        // points.
+        for tweak in [1u8, 2] {
+            let mut bytes = [0u8; 32];
+            bytes[31] = tweak;
+            let blinding = BlindingFactor::try_from(bytes).expect("small non-zero scalar");
+            let Ok(shifted) = self.reblind(&blinding) else {
+                continue;
+            };
+            let scalar = Scalar::from(SecretKey::from(blinding));
+            for parity in [0x02u8, 0x03] {
+                let mut key = self.to_bytes();
+                key[0] = parity;
+                let point = PublicKey::from_slice(&key)
+                    .expect("commitment x coordinate is always on the curve");
+                let Ok(sum) = point.add_exp_tweak(SECP256K1, &scalar) else {
+                    continue;
+                };
+                if sum.serialize()[1..] == shifted.to_bytes()[1..] {
+                    return point.serialize_uncompressed();
+                }
+            }
+        }
+        unreachable!("commitment is equal to two different points")
+    }
+
+    /// Constructs commitment from an uncompressed secp256k1 point, as
+    /// produced by [`PedersenCommitment::to_uncompressed`].
+    pub fn from_uncompressed(data: &[u8; 65]) -> Result<Self, InvalidUncompressedPoint> {
+        secp256k1_zkp::PublicKey::from_slice(data).map_err(|_| InvalidUncompressedPoint)?;
+        for flag in [0x08u8, 0x09] {
+            let mut compressed = [flag; PEDERSEN_COMMITMENT_LEN];
+            compressed[1..].copy_from_slice(&data[1..PEDERSEN_COMMITMENT_LEN]);
+            let commitment = secp256k1_zkp::PedersenCommitment::from_slice(&compressed)
+                .map(PedersenCommitment::from)
+                .map_err(|_| InvalidUncompressedPoint)?;
+            if &commitment.to_uncompressed() == data {
+                return Ok(commitment);
+            }
+        }
+        Err(InvalidUncompressedPoint)
+    }
+}
+
+#[cfg(any(test, feature = "test-helpers"))]
+impl PedersenCommitment {
+    /// Constructs a commitment which is distinct for each `seed`, without
+    /// using a random number generator. The commitment is a valid curve point
+    /// committing to the `seed` value with a fixed blinding factor.
+    ///
+    /// NB: The blinding factor is publicly known, so the commitment must never
+    /// be used outside of tests.
+    pub fn test_unique(seed: u8) -> Self {
+        let blinding = BlindingFactor::try_from([0x01; 32]).expect("hardcoded blinding factor");
+        PedersenCommitment::commit(&RevealedValue::with(seed as u64, blinding))
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+/// data do not represent an uncompressed secp256k1 point.
+pub struct InvalidUncompressedPoint;
+
+impl CommitVerify<RevealedValue, UntaggedProtocol> for PedersenCommitment {
+    fn commit(revealed: &RevealedValue) -> Self {
+        use secp256k1_zkp::Tweak;
+
+        let blinding = Tweak::from_inner(revealed.blinding.0.into_inner())
+            .expect("type guarantees of BlindingFactor are broken");
+        let FungibleState::Bits64(value) = revealed.value;
+
+        secp256k1_zkp::PedersenCommitment::new(SECP256K1, value, blinding, revealed.generator())
+            .into()
+    }
+}
+
+/// A dumb placeholder for a future bulletproofs.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictType, StrictEncode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct NoiseDumb(Array<u8, 512>);
+
+impl Default for NoiseDumb {
+    fn default() -> Self { NoiseDumb::with_rng(&mut thread_rng()) }
+}
+
+// The placeholder has a fixed length, and truncated data are reported as such
+// instead of a generic I/O error.
+impl StrictDecode for NoiseDumb {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            r.read_field::<Array<u8, 512>>()
+                .map(NoiseDumb)
+                .map_err(|err| match err {
+                    DecodeError::Io(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                        DecodeError::DataIntegrityError(s!(
+                            "range proof placeholder must be exactly 512 bytes long"
+                        ))
+                    }
+                    err => err,
+                })
+        })
+    }
+}
+
+impl NoiseDumb {
+    /// Generates random noise using the provided random number generator.
+    pub fn with_rng<R: Rng + RngCore>(rng: &mut R) -> Self {
+        let mut dumb = [0u8; 512];
+        rng.fill(&mut dumb);
+        NoiseDumb(dumb.into())
+    }
+}
+
+/// Range proof value.
+///
+/// Range proofs must be used alongside [`PedersenCommitment`]s to ensure that
+/// the value do not overflow on arithmetic operations with the commitments.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB, tags = custom)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub enum RangeProof {
+    /// Bulletproof for a 64-bit value produced by an external prover.
+    ///
+    /// Bulletproofs verification is not yet supported by this library, thus
+    /// the proof always fails validation with
+    /// [`RangeProofError::BulletproofsAbsent`].
+    #[strict_type(tag = 0x01)]
+    Bulletproof(Array<u8, BULLETPROOF_LEN>),
+
+    /// Public amount committed with [`PedersenCommitment::commit_public`], see
+    /// [`ConcealedValue::public`].
+    ///
+    /// No proof is required, since the value is known to fit into 64 bits;
+    /// validation checks that the value matches the commitment instead.
+    #[strict_type(tag = 0x02)]
+    Public(u64),
+
+    /// Value used when bulletproofs library is not available.
+    ///
+    /// Always fails validation if no source value is given.
+    #[strict_type(tag = 0xFF)]
+    Placeholder(NoiseDumb),
+}
+
+/// Length of a serialized bulletproof for a single 64-bit value.
+pub const BULLETPROOF_LEN: usize = 675;
 
 impl Default for RangeProof {
     fn default() -> Self { RangeProof::Placeholder(default!()) }
 }
 
+impl RangeProof {
+    /// Detects whether the range proof is a placeholder, which can't be
+    /// verified.
+    pub fn is_placeholder(&self) -> bool { matches!(self, RangeProof::Placeholder(_)) }
+
+    /// Returns bit width of the value range proved by the range proof, or
+    /// `None` for the placeholder.
+    pub fn bits(&self) -> Option<u8> {
+        match self {
+            RangeProof::Bulletproof(_) | RangeProof::Public(_) => Some(64),
+            RangeProof::Placeholder(_) => None,
+        }
+    }
+
+    /// Returns serialized range proof, or `None` for public amounts and the
+    /// placeholder.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            RangeProof::Bulletproof(proof) => Some(proof.as_slice()),
+            RangeProof::Public(_) | RangeProof::Placeholder(_) => None,
+        }
+    }
+}
+
 pub struct PedersenProtocol;
 
 impl CommitmentProtocol for PedersenProtocol {}
@@ -394,17 +1464,181 @@ impl CommitVerify<RevealedValue, PedersenProtocol> for ConcealedValue {
 }
 
 impl ConcealedValue {
+    /// Constructs confidential state from a commitment and a range proof
+    /// produced externally, for instance by a separate bulletproofs prover.
+    ///
+    /// The range proof is not verified; use [`Self::verify_range_proof`].
+    pub fn from_parts(commitment: PedersenCommitment, range_proof: RangeProof) -> Self {
+        ConcealedValue {
+            commitment,
+            range_proof,
+        }
+    }
+
+    /// Constructs fungible state with a public `value` committed using
+    /// [`PedersenCommitment::commit_public`], which can be used alongside
+    /// confidential fungible state in the same operation.
+    ///
+    /// Instead of a bulletproof the range proof is [`RangeProof::Public`]
+    /// holding the value itself, so the range proof verification passes
+    /// without bulletproofs support. See [`PedersenCommitment::commit_public`]
+    /// for the privacy implications.
+    pub fn public(value: u64) -> Self {
+        ConcealedValue {
+            commitment: PedersenCommitment::commit_public(value),
+            range_proof: RangeProof::Public(value),
+        }
+    }
+
+    /// Returns the amount of the fungible state constructed with
+    /// [`ConcealedValue::public`], or `None` if the amount is confidential or
+    /// the public amount doesn't match the commitment.
+    pub fn public_value(&self) -> Option<u64> {
+        match self.range_proof {
+            RangeProof::Public(value)
+                if PedersenCommitment::commit_public(value) == self.commitment =>
+            {
+                Some(value)
+            }
+            RangeProof::Bulletproof(_) | RangeProof::Public(_) | RangeProof::Placeholder(_) => None,
+        }
+    }
+
     /// Verifies bulletproof against the commitment.
     pub fn verify(&self) -> bool {
         match self.range_proof {
-            RangeProof::Placeholder(_) => false,
+            RangeProof::Bulletproof(_) | RangeProof::Placeholder(_) => false,
+            RangeProof::Public(_) => self.public_value().is_some(),
         }
     }
 }
 
+/// Cache of range proof verification results with least-recently-used
+/// eviction policy, see [`ConcealedValue::verify_range_proof_cached`].
+///
+/// Entries are keyed by the hash of both the commitment and the range proof
+/// data. Lookups, insertions and evictions take constant time: the entries
+/// form a doubly-linked list in the order of their use, indexed by a hash
+/// map.
+#[derive(Clone, Debug)]
+pub struct RangeProofCache {
+    capacity: usize,
+    index: HashMap<Bytes32, usize>,
+    entries: Vec<RangeProofCacheEntry>,
+    /// Most recently used entry.
+    head: Option<usize>,
+    /// Least recently used entry, which is evicted first.
+    tail: Option<usize>,
+    hits: usize,
+    misses: usize,
+}
+
+#[derive(Clone, Debug)]
+struct RangeProofCacheEntry {
+    key: Bytes32,
+    res: Result<bool, RangeProofError>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+impl RangeProofCache {
+    /// Constructs cache keeping up to `capacity` verification results.
+    pub fn new(capacity: usize) -> Self {
+        RangeProofCache {
+            capacity,
+            index: empty!(),
+            entries: empty!(),
+            head: None,
+            tail: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Number of verification results kept in the cache.
+    pub fn len(&self) -> usize { self.index.len() }
+
+    /// Detects whether the cache is empty.
+    pub fn is_empty(&self) -> bool { self.index.is_empty() }
+
+    /// Number of verifications resolved from the cache.
+    pub fn hits(&self) -> usize { self.hits }
+
+    /// Number of verifications which were not present in the cache.
+    pub fn misses(&self) -> usize { self.misses }
+
+    fn get(&mut self, key: Bytes32) -> Option<Result<bool, RangeProofError>> {
+        let Some(&pos) = self.index.get(&key) else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        self.unlink(pos);
+        self.push_front(pos);
+        Some(self.entries[pos].res)
+    }
+
+    fn insert(&mut self, key: Bytes32, res: Result<bool, RangeProofError>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some(&pos) = self.index.get(&key) {
+            self.entries[pos].res = res;
+            self.unlink(pos);
+            self.push_front(pos);
+            return;
+        }
+        let entry = RangeProofCacheEntry {
+            key,
+            res,
+            prev: None,
+            next: None,
+        };
+        let pos = if self.entries.len() < self.capacity {
+            self.entries.push(entry);
+            self.entries.len() - 1
+        } else {
+            let lru = self.tail.expect("full cache has entries");
+            self.unlink(lru);
+            self.index.remove(&self.entries[lru].key);
+            self.entries[lru] = entry;
+            lru
+        };
+        self.index.insert(key, pos);
+        self.push_front(pos);
+    }
+
+    fn unlink(&mut self, pos: usize) {
+        let RangeProofCacheEntry { prev, next, .. } = self.entries[pos];
+        match prev {
+            Some(prev) => self.entries[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.entries[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_front(&mut self, pos: usize) {
+        self.entries[pos].prev = None;
+        self.entries[pos].next = self.head;
+        match self.head {
+            Some(head) => self.entries[head].prev = Some(pos),
+            None => self.tail = Some(pos),
+        }
+        self.head = Some(pos);
+    }
+}
+
 /// Errors verifying range proofs.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
 #[display(doc_comments)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
 pub enum RangeProofError {
     /// invalid blinding factor {0}.
     InvalidBlinding(BlindingFactor),
@@ -413,12 +1647,160 @@ pub enum RangeProofError {
     /// update your software and try again, or ask your software producer to use
     /// latest RGB release.
     BulletproofsAbsent,
+
+    /// public amount {0} doesn't match the Pedersen commitment.
+    PublicValueMismatch(u64),
 }
 
 impl ConcealedValue {
     /// Verifies validity of the range proof.
+    ///
+    /// Range proofs of public amounts (see [`ConcealedValue::public`]) are
+    /// always valid, since the committed value is known to fit into 64 bits.
+    /// Bulletproofs can't be verified by this version of the library and are
+    /// reported as [`RangeProofError::BulletproofsAbsent`].
     pub fn verify_range_proof(&self) -> Result<bool, RangeProofError> {
-        Err(RangeProofError::BulletproofsAbsent)
+        match self.range_proof {
+            RangeProof::Bulletproof(_) | RangeProof::Placeholder(_) => {
+                Err(RangeProofError::BulletproofsAbsent)
+            }
+            RangeProof::Public(_) if self.public_value().is_some() => Ok(true),
+            RangeProof::Public(value) => Err(RangeProofError::PublicValueMismatch(value)),
+        }
+    }
+
+    /// Verifies validity of the range proof, reusing the result of a previous
+    /// verification of the same commitment and range proof from the `cache`.
+    pub fn verify_range_proof_cached(
+        &self,
+        cache: &mut RangeProofCache,
+    ) -> Result<bool, RangeProofError> {
+        let key = self.range_proof_cache_key();
+        if let Some(res) = cache.get(key) {
+            return res;
+        }
+        let res = self.verify_range_proof();
+        cache.insert(key, res);
+        res
+    }
+
+    /// Hash binding both the commitment and the full range proof data, so
+    /// that a cached verification result can't be reused for a different
+    /// proof of the same commitment or vice versa.
+    fn range_proof_cache_key(&self) -> Bytes32 {
+        let proof = self
+            .range_proof
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .expect("in-memory writer doesn't fail")
+            .unbox();
+        let mut engine = Sha256::default();
+        engine.update(self.commitment_bytes());
+        engine.update(proof);
+        Bytes32::from_inner(engine.finalize().into())
+    }
+
+    /// Returns compressed serialization of the Pedersen commitment point.
+    pub fn commitment_bytes(&self) -> [u8; PEDERSEN_COMMITMENT_LEN] { self.commitment.to_bytes() }
+
+    /// Verifies that the disclosed revealed value and blinding factor match
+    /// the Pedersen commitment of this concealed value.
+    ///
+    /// Range proof is not checked; use [`Self::verify_range_proof`] for that.
+    ///
+    /// The commitments are compared in constant time over their serialized
+    /// form, so the verification doesn't leak through timing which part of the
+    /// commitment doesn't match.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeProofError::InvalidBlinding`] if the commitment to the
+    /// revealed state doesn't match [`ConcealedValue::commitment`].
+    pub fn verify_disclosure(&self, revealed: &RevealedValue) -> Result<(), RangeProofError> {
+        let commitment = PedersenCommitment::commit(revealed).to_bytes();
+        if !bool::from(commitment.ct_eq(&self.commitment_bytes())) {
+            return Err(RangeProofError::InvalidBlinding(revealed.blinding));
+        }
+        Ok(())
+    }
+}
+
+/// Selective disclosure of the fungible state, allowing the recipient of a
+/// payment to verify the amount against the commitment in the operation.
+///
+/// See [`RevealedValue::to_disclosure`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct Disclosure {
+    /// Type of the owned state the value belongs to.
+    pub assignment_type: schema::AssignmentType,
+    /// Disclosed value in smallest indivisible units.
+    pub value: u64,
+    /// Blinding factor used in the Pedersen commitment to the value.
+    pub blinding: BlindingFactor,
+    /// Tag of the contract-scoped asset generator used in the commitment,
+    /// see [`RevealedValue::generator_tag`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub generator_tag: Option<Bytes32>,
+}
+
+// Invalid blinding factors are rejected for the same reason as for
+// `RevealedValue`.
+impl StrictDecode for Disclosure {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_struct(|r| {
+            let assignment_type = r.read_field(fname!("assignmentType"))?;
+            let value = r.read_field(fname!("value"))?;
+            let blinding: BlindingFactor = r.read_field(fname!("blinding"))?;
+            secp256k1_zkp::SecretKey::from_slice(blinding.as_slice()).map_err(|_| {
+                DecodeError::DataIntegrityError(s!("invalid or zero blinding factor"))
+            })?;
+            let generator_tag = r.read_field(fname!("generatorTag"))?;
+            Ok(Disclosure {
+                assignment_type,
+                value,
+                blinding,
+                generator_tag,
+            })
+        })
+    }
+}
+
+/// Errors verifying [`Disclosure`] with [`Disclosure::verify_against`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum DisclosureError {
+    /// disclosed blinding factor {0} is not a valid secp256k1 scalar.
+    InvalidBlinding(BlindingFactor),
+
+    /// disclosed value {0} and its blinding factor don't match the commitment.
+    Mismatch(u64),
+}
+
+impl Disclosure {
+    /// Verifies that the disclosed value and blinding factor open the
+    /// `commitment`, using [`ConcealedValue::verify_disclosure`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DisclosureError::InvalidBlinding`] if the blinding factor is
+    /// not a valid secp256k1 scalar, and [`DisclosureError::Mismatch`] if the
+    /// disclosure doesn't match the commitment.
+    pub fn verify_against(&self, commitment: &ConcealedValue) -> Result<(), DisclosureError> {
+        secp256k1_zkp::SecretKey::from_slice(self.blinding.as_slice())
+            .map_err(|_| DisclosureError::InvalidBlinding(self.blinding))?;
+        commitment
+            .verify_disclosure(&RevealedValue {
+                value: self.value.into(),
+                blinding: self.blinding,
+                generator_tag: self.generator_tag,
+            })
+            .map_err(|_| DisclosureError::Mismatch(self.value))
     }
 }
 
@@ -442,4 +1824,965 @@ mod test {
             .collect::<HashSet<_>>();
         assert_eq!(generators.len(), 1);
     }
+
+    #[test]
+    fn generator_tag_stability() {
+        assert_eq!(
+            asset_generator_tag().to_hex(),
+            "50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0"
+        );
+        assert_eq!(asset_generator(), asset_generator());
+    }
+
+    #[test]
+    fn fungible_u64_eq() {
+        let state = FungibleState::Bits64(15);
+        assert_eq!(state.try_as_u64(), Some(15));
+        assert!(state == 15u64);
+        assert!(state != 16u64);
+    }
+
+    #[test]
+    fn fungible_be_bytes() {
+        for val in [0u64, 1, u64::MAX] {
+            let state = FungibleState::Bits64(val);
+            let bytes = state.to_be_bytes();
+            assert_eq!(bytes, val.to_be_bytes());
+            assert_eq!(FungibleState::from_be_bytes(&bytes), Ok(state));
+        }
+        assert_eq!(FungibleState::Bits64(1).to_be_bytes(), [0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(FungibleState::from_be_bytes(&[0u8; 7]), Err(FungibleLenError(7)));
+    }
+
+    #[test]
+    fn fungible_bounds() {
+        assert_eq!(FungibleState::ZERO.as_u64(), 0);
+        assert_eq!(FungibleState::MAX.as_u64(), u64::MAX);
+        assert_eq!(FungibleState::ZERO, FungibleState::default());
+    }
+
+    #[test]
+    fn fungible_conversion() {
+        assert_eq!(u64::try_from(FungibleState::Bits64(15)), Ok(15));
+        assert_eq!(u64::try_from(FungibleState::MAX), Ok(u64::MAX));
+        assert_eq!(FungibleState::MAX.try_as_u64(), Some(u64::MAX));
+
+        // There are no fungible state variants yet which may overflow or be
+        // negative; check the errors are reported with the state type.
+        assert_eq!(
+            FungibleConversionError::Overflow(schema::FungibleType::Unsigned64Bit).to_string(),
+            "fungible state of 64bit type has value exceeding the range of the target integer \
+             type."
+        );
+        assert_eq!(
+            FungibleConversionError::Negative(schema::FungibleType::Unsigned64Bit).to_string(),
+            "fungible state of 64bit type has negative value, which can't be represented by an \
+             unsigned integer type."
+        );
+    }
+
+    #[test]
+    fn fungible_grouped() {
+        assert_eq!(FungibleState::ZERO.grouped(','), "0");
+        assert_eq!(FungibleState::Bits64(999).grouped(','), "999");
+        assert_eq!(FungibleState::Bits64(1000).grouped(','), "1,000");
+        assert_eq!(FungibleState::Bits64(123456).grouped(' '), "123 456");
+        assert_eq!(FungibleState::Bits64(1_000_000).grouped('\''), "1'000'000");
+        assert_eq!(FungibleState::MAX.grouped(','), "18,446,744,073,709,551,615");
+        assert_eq!(FungibleState::MAX.grouped('_'), "18_446_744_073_709_551_615");
+        assert_eq!(FungibleState::MAX.to_string(), "18446744073709551615");
+    }
+
+    #[test]
+    fn fungible_cmp_value() {
+        let values = [FungibleState::ZERO, FungibleState::Bits64(5), FungibleState::MAX];
+        for a in values {
+            for b in values {
+                assert_eq!(a.cmp_value(&b), Some(a.as_u64().cmp(&b.as_u64())));
+                assert_eq!(a.cmp_value(&b), Some(a.cmp(&b)));
+            }
+        }
+    }
+
+    #[test]
+    fn fungible_saturating_wrapping() {
+        let max = FungibleState::MAX;
+        let zero = FungibleState::ZERO;
+        let one = FungibleState::Bits64(1);
+
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(zero.saturating_sub(one), zero);
+        assert_eq!(one.saturating_add(one), FungibleState::Bits64(2));
+        assert_eq!(one.saturating_sub(one), zero);
+
+        assert_eq!(max.wrapping_add(one), zero);
+        assert_eq!(zero.wrapping_sub(one), max);
+        assert_eq!(max.wrapping_add(max), FungibleState::Bits64(u64::MAX - 1));
+        assert_eq!(one.wrapping_sub(1u64), zero);
+    }
+
+    #[test]
+    fn split_balance() {
+        let input = RevealedValue::new(100u64, &mut thread_rng());
+        let input_commitment = PedersenCommitment::commit(&input);
+
+        for parts in [&[100u64][..], &[40, 60], &[1, 0, 33, 66]] {
+            let outputs =
+                RevealedValue::split(100, parts, &input.blinding, &mut thread_rng()).unwrap();
+            assert_eq!(outputs.len(), parts.len());
+            for (output, part) in outputs.iter().zip(parts) {
+                assert_eq!(output.value, *part);
+            }
+            let commitments = outputs
+                .iter()
+                .map(PedersenCommitment::commit)
+                .collect::<Vec<_>>();
+            assert!(PedersenCommitment::verify_sum(&[input_commitment], &commitments));
+        }
+
+        let wrong = RevealedValue::new(60u64, &mut thread_rng());
+        assert!(!PedersenCommitment::verify_sum(&[input_commitment], &[
+            PedersenCommitment::commit(&RevealedValue::new(40u64, &mut thread_rng())),
+            PedersenCommitment::commit(&wrong)
+        ]));
+
+        assert_eq!(
+            RevealedValue::split(100, &[40, 50], &input.blinding, &mut thread_rng()),
+            Err(SplitError::SumMismatch)
+        );
+        assert_eq!(
+            RevealedValue::split(100, &[u64::MAX, 101], &input.blinding, &mut thread_rng()),
+            Err(SplitError::SumMismatch)
+        );
+        assert_eq!(
+            RevealedValue::split(0, &[], &input.blinding, &mut thread_rng()),
+            Err(SplitError::NoParts)
+        );
+
+        let zero = BlindingFactor::from_hex(&"00".repeat(32)).unwrap();
+        assert_eq!(
+            RevealedValue::split(100, &[40, 60], &zero, &mut thread_rng()),
+            Err(SplitError::InvalidBlinding(zero))
+        );
+    }
+
+    #[test]
+    fn blinding_derivation() {
+        let seed = [0x11u8; 32];
+        let contract = [0x22u8; 32];
+        for (index, hex) in [
+            (0, "b39c34e40696ab8d86d63a48e878dbb320586aeefbb58e0fa1a71df0737b9dff"),
+            (1, "15b667853b95ad2c0049d3f7190bd7321efe52b4990003af8e0cb1b3d96c5da7"),
+            (u32::MAX, "b11cc1d4cbe06cd0309abb3714445cf9ffe23a6d5f9725c3ee1fca331457e865"),
+        ] {
+            let blinding = BlindingFactor::derive(&seed, contract, index).unwrap();
+            assert_eq!(blinding, BlindingFactor::from_hex(hex).unwrap());
+            assert_eq!(blinding, BlindingFactor::derive(&seed, contract, index).unwrap());
+        }
+        assert_ne!(
+            BlindingFactor::derive(&seed, contract, 0),
+            BlindingFactor::derive(&[0x12u8; 32], contract, 0)
+        );
+        assert_ne!(
+            BlindingFactor::derive(&seed, contract, 0),
+            BlindingFactor::derive(&seed, [0x23u8; 32], 0)
+        );
+    }
+
+    #[test]
+    fn value_builder() {
+        use secp256k1_zkp::rand::rngs::StdRng;
+        use secp256k1_zkp::rand::SeedableRng;
+
+        let random = RevealedValue::builder(15u64)
+            .random_blinding(&mut StdRng::seed_from_u64(1))
+            .build()
+            .unwrap();
+        assert_eq!(random, RevealedValue::new(15u64, &mut StdRng::seed_from_u64(1)));
+
+        let seed = [0x11u8; 32];
+        let derived = RevealedValue::builder(15u64)
+            .derived_blinding(&seed, [0x22u8; 32], 1)
+            .build()
+            .unwrap();
+        assert_eq!(derived.value, FungibleState::Bits64(15));
+        assert_eq!(derived.blinding, BlindingFactor::derive(&seed, [0x22u8; 32], 1).unwrap());
+
+        let blinding = BlindingFactor::try_from([1u8; 32]).unwrap();
+        let explicit = RevealedValue::builder(15u64)
+            .random_blinding(&mut thread_rng())
+            .explicit_blinding(blinding)
+            .build();
+        assert_eq!(explicit, Ok(RevealedValue::with(15u64, blinding)));
+
+        assert_eq!(RevealedValue::builder(15u64).build(), Err(ValueBuildError::NoBlinding));
+        for invalid in [[0u8; 32], [0xFFu8; 32]] {
+            let blinding = BlindingFactor::from_hex(&invalid.to_hex()).unwrap();
+            assert_eq!(
+                RevealedValue::builder(15u64)
+                    .explicit_blinding(blinding)
+                    .build(),
+                Err(ValueBuildError::InvalidBlinding)
+            );
+        }
+    }
+
+    #[test]
+    fn uncompressed_commitment() {
+        let mut rng = thread_rng();
+        for value in [0u64, 1, 15, u64::MAX] {
+            for _ in 0..8 {
+                let commitment = PedersenCommitment::commit(&RevealedValue::new(value, &mut rng));
+                let uncompressed = commitment.to_uncompressed();
+                assert_eq!(uncompressed[0], 0x04);
+                assert_eq!(uncompressed[1..33], commitment.to_bytes()[1..]);
+                assert_eq!(PedersenCommitment::from_uncompressed(&uncompressed), Ok(commitment));
+
+                // the opposite point has the same x coordinate
+                let mut negated = uncompressed;
+                negated[1..].copy_from_slice(
+                    &secp256k1_zkp::PublicKey::from_slice(&uncompressed)
+                        .unwrap()
+                        .negate(SECP256K1)
+                        .serialize_uncompressed()[1..],
+                );
+                let opposite = PedersenCommitment::from_uncompressed(&negated).unwrap();
+                assert_ne!(opposite, commitment);
+                assert_eq!(opposite.to_bytes()[1..], commitment.to_bytes()[1..]);
+                assert_eq!(opposite.to_uncompressed(), negated);
+
+                let mut invalid = uncompressed;
+                invalid[0] = 0x02;
+                assert_eq!(
+                    PedersenCommitment::from_uncompressed(&invalid),
+                    Err(InvalidUncompressedPoint)
+                );
+                invalid[0] = 0x04;
+                invalid[64] ^= 0x01;
+                assert_eq!(
+                    PedersenCommitment::from_uncompressed(&invalid),
+                    Err(InvalidUncompressedPoint)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fixed_bytes() {
+        let blinding = BlindingFactor::try_from([0x11; 32]).unwrap();
+        let revealed = RevealedValue::with(0x0102_0304_0506_0708u64, blinding);
+        let bytes = revealed.to_bytes();
+        assert_eq!(bytes[..8], [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(bytes[8..], [0x11; 32]);
+        assert_eq!(RevealedValue::from_bytes(bytes), Ok(revealed));
+
+        let mut rng = thread_rng();
+        for value in [0u64, 1, u64::MAX] {
+            let revealed = RevealedValue::new(value, &mut rng);
+            assert_eq!(RevealedValue::from_bytes(revealed.to_bytes()), Ok(revealed));
+        }
+
+        for invalid in [[0u8; 32], [0xFFu8; 32]] {
+            let mut bytes = revealed.to_bytes();
+            bytes[8..].copy_from_slice(&invalid);
+            assert_eq!(RevealedValue::from_bytes(bytes), Err(FieldOrderOverflow));
+        }
+    }
+
+    #[test]
+    fn range_proof_accessors() {
+        let placeholder = RangeProof::default();
+        assert!(placeholder.is_placeholder());
+        assert_eq!(placeholder.as_bytes(), None);
+        let placeholder = RangeProof::Placeholder(NoiseDumb::with_rng(&mut thread_rng()));
+        assert!(placeholder.is_placeholder());
+        assert_eq!(placeholder.as_bytes(), None);
+
+        let proof = RangeProof::Bulletproof(Array::from([0xA5u8; BULLETPROOF_LEN]));
+        assert!(!proof.is_placeholder());
+        assert_eq!(proof.as_bytes(), Some(&[0xA5u8; BULLETPROOF_LEN][..]));
+        assert_eq!(proof.bits(), Some(64));
+    }
+
+    #[test]
+    fn merge() {
+        let mut rng = thread_rng();
+        for (a, b) in [(0u64, 0u64), (5, 7), (0, u64::MAX), (u64::MAX / 2, u64::MAX / 2 + 1)] {
+            let first = RevealedValue::new(a, &mut rng);
+            let second = RevealedValue::new(b, &mut rng);
+            let merged = first.merge(&second).unwrap();
+            assert_eq!(merged.value, FungibleState::Bits64(a + b));
+            assert_eq!(merged, second.merge(&first).unwrap());
+            assert!(secp256k1_zkp::verify_commitments_sum_to_equal(
+                SECP256K1,
+                &[PedersenCommitment::commit(&first).0, PedersenCommitment::commit(&second).0],
+                &[PedersenCommitment::commit(&merged).0]
+            ));
+        }
+
+        let first = RevealedValue::new(u64::MAX, &mut rng);
+        let second = RevealedValue::new(1u64, &mut rng);
+        assert_eq!(first.merge(&second), Err(MergeError::ValueOverflow));
+
+        let opposite =
+            BlindingFactor::from(secp256k1_zkp::SecretKey::from(first.blinding).negate());
+        let second = RevealedValue::with(0u64, opposite);
+        assert_eq!(first.merge(&second), Err(MergeError::ZeroBlinding));
+    }
+
+    #[test]
+    fn reblind() {
+        let mut rng = thread_rng();
+        for value in [0u64, 1, 15, u64::MAX] {
+            for _ in 0..8 {
+                let revealed = RevealedValue::new(value, &mut rng);
+                let add = RevealedValue::new(0u64, &mut rng).blinding;
+                let commitment = PedersenCommitment::commit(&revealed);
+                let reblinded = commitment.reblind(&add).unwrap();
+                assert_ne!(reblinded, commitment);
+
+                let mut opened = revealed;
+                opened.reblind(&add).unwrap();
+                assert_eq!(opened.value, revealed.value);
+                assert_ne!(opened.blinding, revealed.blinding);
+                assert_eq!(PedersenCommitment::commit(&opened), reblinded);
+                let negated = BlindingFactor::from(secp256k1_zkp::SecretKey::from(add).negate());
+                assert_eq!(reblinded.reblind(&negated).unwrap(), commitment);
+            }
+        }
+
+        let mut revealed = RevealedValue::new(0u64, &mut rng);
+        let opposite =
+            BlindingFactor::from(secp256k1_zkp::SecretKey::from(revealed.blinding).negate());
+        assert_eq!(
+            PedersenCommitment::commit(&revealed).reblind(&opposite),
+            Err(ReblindError::ZeroBlinding)
+        );
+        let original = revealed;
+        assert_eq!(revealed.reblind(&opposite), Err(ReblindError::ZeroBlinding));
+        assert_eq!(revealed, original);
+
+        // Blinding factors parsed from arbitrary data may be invalid scalars
+        let commitment = PedersenCommitment::commit(&revealed);
+        for invalid in [[0u8; 32], [0xFFu8; 32]] {
+            let invalid = BlindingFactor::from_hex(&invalid.to_hex()).unwrap();
+            assert_eq!(commitment.reblind(&invalid), Err(ReblindError::InvalidBlinding(invalid)));
+            assert_eq!(revealed.reblind(&invalid), Err(ReblindError::InvalidBlinding(invalid)));
+            assert_eq!(revealed, original);
+
+            let mut corrupted = RevealedValue {
+                blinding: invalid,
+                ..revealed
+            };
+            assert_eq!(
+                corrupted.reblind(&BlindingFactor::public()),
+                Err(ReblindError::InvalidBlinding(invalid))
+            );
+            assert_eq!(corrupted.merge(&revealed), Err(MergeError::InvalidBlinding(invalid)));
+            assert_eq!(revealed.merge(&corrupted), Err(MergeError::InvalidBlinding(invalid)));
+        }
+    }
+
+    #[test]
+    fn amount_conversions() {
+        let atomic = AtomicAmount::from(150u64);
+        assert_eq!(FungibleState::from(atomic), FungibleState::Bits64(150));
+        assert_eq!(AtomicAmount::from(FungibleState::Bits64(150)), atomic);
+        assert_eq!(RevealedValue::new(atomic, &mut thread_rng()).value, FungibleState::Bits64(150));
+
+        let display = atomic.to_display(2);
+        assert_eq!(display, DisplayAmount {
+            units: 150,
+            precision: 2
+        });
+        assert_eq!(display.to_string(), "1.50");
+        assert_eq!(atomic.to_display(0).to_string(), "150");
+        assert_eq!(atomic.to_display(5).to_string(), "0.00150");
+        assert_eq!(display.to_atomic(2), Ok(atomic));
+        assert_eq!(display.to_atomic(4), Ok(AtomicAmount::from(15000u64)));
+        assert_eq!(display.to_atomic(1), Ok(AtomicAmount::from(15u64)));
+
+        // rounding is rejected
+        assert_eq!(display.to_atomic(0), Err(AmountError::PrecisionLoss(display, 0)));
+        let tiny = DisplayAmount {
+            units: 1,
+            precision: u8::MAX,
+        };
+        assert_eq!(tiny.to_atomic(8), Err(AmountError::PrecisionLoss(tiny, 8)));
+
+        let max = AtomicAmount::from(u64::MAX).to_display(0);
+        assert_eq!(max.to_atomic(1), Err(AmountError::Overflow(max, 1)));
+        assert_eq!(max.to_atomic(u8::MAX), Err(AmountError::Overflow(max, u8::MAX)));
+        let zero = DisplayAmount {
+            units: 0,
+            precision: 0,
+        };
+        assert_eq!(zero.to_atomic(u8::MAX), Ok(AtomicAmount::default()));
+    }
+
+    #[test]
+    fn values_batch() {
+        assert_eq!(encode_values(&[]), vec![0u8; 4]);
+        assert_eq!(decode_values(&encode_values(&[])).unwrap(), vec![]);
+
+        let values = [1u64, 0, u64::MAX].map(|value| RevealedValue::new(value, &mut thread_rng()));
+        let data = encode_values(&values);
+        assert_eq!(&data[..4], &[3, 0, 0, 0]);
+        assert_eq!(data.len(), 4 + 3 * 42);
+        assert_eq!(decode_values(&data).unwrap(), values);
+
+        let mut extended = data.clone();
+        extended.push(0);
+        assert!(decode_values(&extended).is_err());
+        assert!(decode_values(&data[..data.len() - 1]).is_err());
+        assert!(decode_values(&[]).is_err());
+        assert!(decode_values(&[0xFF; 4]).is_err());
+    }
+
+    #[test]
+    fn contract_commitments() {
+        let contract1 = ContractId::from([1u8; 32]);
+        let contract2 = ContractId::from([2u8; 32]);
+        assert_ne!(contract_generator_tag(contract1), contract_generator_tag(contract2));
+        assert_ne!(contract_generator_tag(contract1), asset_generator_tag());
+        assert_eq!(contract_asset_generator(contract1), contract_asset_generator(contract1));
+
+        let value1 = RevealedValue::new_for_contract(100u64, contract1, &mut thread_rng());
+        assert_eq!(value1.generator_tag, Some(contract_generator_tag(contract1).into()));
+        assert_eq!(value1.generator(), contract_asset_generator(contract1));
+        let value2 = RevealedValue {
+            generator_tag: Some(contract_generator_tag(contract2).into()),
+            ..value1
+        };
+        let legacy = RevealedValue::with(100u64, value1.blinding);
+        assert_eq!(legacy.generator_tag, None);
+        assert_eq!(legacy.generator(), asset_generator());
+
+        let commitment1 = PedersenCommitment::commit(&value1);
+        let commitment2 = PedersenCommitment::commit(&value2);
+        assert_ne!(commitment1, commitment2);
+        assert_ne!(commitment1, PedersenCommitment::commit(&legacy));
+        assert_eq!(commitment2, PedersenCommitment::commit(&legacy.bind_to_contract(contract2)));
+        assert_ne!(value1.state_commitment(), value2.state_commitment());
+        let commit_encode = |value: &RevealedValue| {
+            let mut data = vec![];
+            value.commit_encode(&mut data);
+            data
+        };
+        assert_ne!(commit_encode(&value1), commit_encode(&value2));
+
+        let parts = RevealedValue::split(100, &[30, 70], &value1.blinding, &mut thread_rng())
+            .unwrap()
+            .into_iter()
+            .map(|part| part.bind_to_contract(contract1))
+            .collect::<Vec<_>>();
+        let outputs = parts
+            .iter()
+            .map(PedersenCommitment::commit)
+            .collect::<Vec<_>>();
+        assert!(PedersenCommitment::verify_sum(&[commitment1], &outputs));
+        assert!(!PedersenCommitment::verify_sum(&[commitment2], &outputs));
+        assert_eq!(parts[0].merge(&parts[1]).unwrap().generator_tag, value1.generator_tag);
+        assert_eq!(parts[0].merge(&value2), Err(MergeError::GeneratorMismatch));
+        assert_eq!(legacy.merge(&value1), Err(MergeError::GeneratorMismatch));
+
+        let ty = schema::AssignmentType::with(4);
+        let concealed = ConcealedValue::from_parts(commitment1, RangeProof::default());
+        assert_eq!(value1.to_disclosure(ty).verify_against(&concealed), Ok(()));
+        assert_eq!(
+            legacy.to_disclosure(ty).verify_against(&concealed),
+            Err(DisclosureError::Mismatch(100))
+        );
+
+        let data = value1
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        let decoded =
+            RevealedValue::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap();
+        assert_eq!(decoded, value1);
+    }
+
+    #[test]
+    fn commitment_sum() {
+        let values = [3u64, 5, 9, 0, u64::MAX - 17]
+            .map(|value| RevealedValue::new(value, &mut thread_rng()));
+        let commitments = values.map(|value| PedersenCommitment::commit(&value));
+        assert_eq!(PedersenCommitment::sum(&[]), None);
+        assert_eq!(PedersenCommitment::sum(&commitments[..1]), Some(commitments[0]));
+
+        let total = values[1..3]
+            .iter()
+            .try_fold(values[0], |acc, value| acc.merge(value))
+            .unwrap();
+        let sum = PedersenCommitment::sum(&commitments[..3]).unwrap();
+        assert_eq!(sum, PedersenCommitment::commit(&total));
+        assert!(PedersenCommitment::verify_sum(&commitments[..3], &[sum]));
+        let wrong = RevealedValue::with(18u64, total.blinding);
+        assert_ne!(sum, PedersenCommitment::commit(&wrong));
+
+        let sum = PedersenCommitment::sum(&commitments).unwrap();
+        assert!(PedersenCommitment::verify_sum(&commitments, &[sum]));
+    }
+
+    #[test]
+    fn disclosure() {
+        let ty = schema::AssignmentType::with(4);
+        let revealed = RevealedValue::new(250, &mut thread_rng());
+        let concealed = ConcealedValue::from_parts(
+            PedersenCommitment::commit(&revealed),
+            RangeProof::default(),
+        );
+        let disclosure = revealed.to_disclosure(ty);
+        assert_eq!(disclosure.assignment_type, ty);
+        assert_eq!(disclosure.value, 250);
+        assert_eq!(disclosure.blinding, revealed.blinding);
+        assert_eq!(disclosure.verify_against(&concealed), Ok(()));
+
+        let data = disclosure
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        assert_eq!(data.len(), 2 + 8 + 32 + 1);
+        let decoded =
+            Disclosure::strict_decode(&mut StrictReader::in_memory(data.clone(), usize::MAX))
+                .unwrap();
+        assert_eq!(decoded, disclosure);
+
+        let mut zero = data;
+        zero[10..].fill(0);
+        assert!(matches!(
+            Disclosure::strict_decode(&mut StrictReader::in_memory(zero, usize::MAX)),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+
+        let other = ConcealedValue::from_parts(
+            PedersenCommitment::commit(&RevealedValue::new(250, &mut thread_rng())),
+            RangeProof::default(),
+        );
+        assert_eq!(disclosure.verify_against(&other), Err(DisclosureError::Mismatch(250)));
+        let wrong_value = Disclosure {
+            value: 251,
+            ..disclosure
+        };
+        assert_eq!(wrong_value.verify_against(&concealed), Err(DisclosureError::Mismatch(251)));
+
+        let invalid = BlindingFactor::from_hex(&"ff".repeat(32)).unwrap();
+        let invalid_blinding = Disclosure {
+            blinding: invalid,
+            ..disclosure
+        };
+        assert_eq!(
+            invalid_blinding.verify_against(&concealed),
+            Err(DisclosureError::InvalidBlinding(invalid))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn disclosure_serde() {
+        let disclosure = RevealedValue::with(250u64, BlindingFactor::try_from([7u8; 32]).unwrap())
+            .to_disclosure(schema::AssignmentType::with(4));
+        let json = serde_json::to_string(&disclosure).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"assignmentType":4,"value":250,"#,
+                r#""blinding":"0707070707070707070707070707070707070707070707070707070707070707"}"#
+            )
+        );
+        assert_eq!(serde_json::from_str::<Disclosure>(&json).unwrap(), disclosure);
+
+        let zero = json.replace("07", "00");
+        assert!(serde_json::from_str::<Disclosure>(&zero).is_err());
+    }
+
+    #[test]
+    fn commitment_tree() {
+        let values = [3u64, 5, 9, 0, 17].map(|value| RevealedValue::new(value, &mut thread_rng()));
+        let leaves = values.map(|value| PedersenCommitment::commit(&value));
+        let left = PedersenCommitment::sum(&leaves[..2]).unwrap();
+        let right = PedersenCommitment::sum(&leaves[2..]).unwrap();
+        let total = values[1..]
+            .iter()
+            .try_fold(values[0], |acc, value| acc.merge(value))
+            .unwrap();
+        let total = PedersenCommitment::commit(&total);
+
+        assert!(PedersenCommitment::verify_tree(&leaves, &[(0..2, left), (2..5, right)], total));
+        assert!(PedersenCommitment::verify_tree(&leaves, &[(2..5, right), (0..2, left)], total));
+        assert!(PedersenCommitment::verify_tree(&leaves, &[(0..5, total)], total));
+
+        // wrong subtotal or total
+        assert!(!PedersenCommitment::verify_tree(&leaves, &[(0..2, right), (2..5, left)], total));
+        assert!(!PedersenCommitment::verify_tree(&leaves, &[(0..2, left), (2..5, right)], left));
+        // overlapping, incomplete, empty and out of bounds ranges
+        assert!(!PedersenCommitment::verify_tree(&leaves, &[(0..3, left), (2..5, right)], total));
+        assert!(!PedersenCommitment::verify_tree(&leaves, &[(0..2, left)], left));
+        assert!(!PedersenCommitment::verify_tree(
+            &leaves,
+            &[(0..2, left), (2..2, left), (2..5, right)],
+            total
+        ));
+        assert!(!PedersenCommitment::verify_tree(
+            &leaves[..4],
+            &[(0..2, left), (2..5, right)],
+            total
+        ));
+        assert!(!PedersenCommitment::verify_tree(&[], &[], total));
+    }
+
+    #[test]
+    fn public_commitments() {
+        for value in [0u64, 4, u64::MAX] {
+            let public = ConcealedValue::public(value);
+            let opening = RevealedValue::with(value, BlindingFactor::public());
+            assert_eq!(public.commitment, PedersenCommitment::commit_public(value));
+            assert_eq!(public.commitment, PedersenCommitment::commit(&opening));
+            assert_eq!(public.verify_disclosure(&opening), Ok(()));
+            assert_eq!(public.public_value(), Some(value));
+            assert_eq!(public.verify_range_proof(), Ok(true));
+            assert!(public.verify());
+
+            let data = public
+                .strict_encode(StrictWriter::in_memory(usize::MAX))
+                .unwrap()
+                .unbox();
+            assert_eq!(data[PEDERSEN_COMMITMENT_LEN], 0x02);
+            assert_eq!(&data[PEDERSEN_COMMITMENT_LEN + 1..], &value.to_le_bytes());
+            let decoded =
+                ConcealedValue::strict_decode(&mut StrictReader::in_memory(data, usize::MAX))
+                    .unwrap();
+            assert_eq!(decoded.public_value(), Some(value));
+            assert_eq!(decoded.range_proof, RangeProof::Public(value));
+        }
+
+        let forged = ConcealedValue::from_parts(
+            PedersenCommitment::commit_public(5),
+            ConcealedValue::public(6).range_proof,
+        );
+        assert_eq!(forged.public_value(), None);
+        assert_eq!(forged.verify_range_proof(), Err(RangeProofError::PublicValueMismatch(6)));
+        assert!(!forged.verify());
+
+        // zero placeholder produced for the state commitments is not a public
+        // amount, even for the zero value committed with the public blinding
+        let zero = RevealedValue::with(0u64, BlindingFactor::public());
+        let StateCommitment::Fungible(placeholder) = zero.state_commitment() else {
+            unreachable!()
+        };
+        assert_eq!(placeholder.commitment, ConcealedValue::public(0).commitment);
+        assert_ne!(placeholder, ConcealedValue::public(0));
+        assert_eq!(placeholder.public_value(), None);
+        assert_eq!(placeholder.verify_range_proof(), Err(RangeProofError::BulletproofsAbsent));
+        let confidential = ConcealedValue::from_parts(
+            PedersenCommitment::commit(&RevealedValue::new(5, &mut thread_rng())),
+            RangeProof::default(),
+        );
+        assert_eq!(confidential.public_value(), None);
+
+        let secret = RevealedValue::new(6, &mut thread_rng());
+        let total = secret
+            .merge(&RevealedValue::with(4u64, BlindingFactor::public()))
+            .unwrap();
+        assert!(PedersenCommitment::verify_sum(
+            &[PedersenCommitment::commit(&secret), PedersenCommitment::commit_public(4)],
+            &[PedersenCommitment::commit(&total)]
+        ));
+    }
+
+    #[test]
+    fn test_unique_commitments() {
+        use strict_encoding::{StrictReader, StrictWriter};
+
+        let commitments = (0..=u8::MAX)
+            .map(PedersenCommitment::test_unique)
+            .collect::<HashSet<_>>();
+        assert_eq!(commitments.len(), 256);
+        assert_eq!(PedersenCommitment::test_unique(7), PedersenCommitment::test_unique(7));
+
+        for commitment in [PedersenCommitment::test_unique(0), PedersenCommitment::test_unique(42)]
+        {
+            let data = commitment
+                .strict_encode(StrictWriter::in_memory(usize::MAX))
+                .unwrap()
+                .unbox();
+            let mut reader = StrictReader::in_memory(data, usize::MAX);
+            assert_eq!(PedersenCommitment::strict_decode(&mut reader).unwrap(), commitment);
+        }
+    }
+
+    #[test]
+    fn commitment_bytes() {
+        let revealed = RevealedValue::new(15, &mut thread_rng());
+        let commitment = PedersenCommitment::commit(&revealed);
+        let concealed = ConcealedValue {
+            commitment,
+            range_proof: RangeProof::default(),
+        };
+        assert_eq!(concealed.commitment_bytes(), commitment.to_bytes());
+
+        let hex = commitment.to_string();
+        assert_eq!(Vec::<u8>::from_hex(&hex).unwrap(), commitment.to_bytes());
+        assert_eq!(PedersenCommitment::from_str(&hex).unwrap(), commitment);
+        assert_eq!(
+            PedersenCommitment::from_str(&commitment.to_bytes().to_hex()).unwrap(),
+            commitment
+        );
+    }
+
+    #[test]
+    fn revealed_blinding_decode() {
+        use strict_encoding::{StrictReader, StrictWriter};
+
+        let revealed = RevealedValue::new(15, &mut thread_rng());
+        let data = revealed
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        let decode = |data: Vec<u8>| {
+            RevealedValue::strict_decode(&mut StrictReader::in_memory(data, usize::MAX))
+        };
+        assert_eq!(decode(data.clone()).unwrap(), revealed);
+
+        let blinding_pos = data.len() - 33;
+        for invalid in [[0u8; 32], [0xFFu8; 32]] {
+            let mut data = data.clone();
+            data[blinding_pos..blinding_pos + 32].copy_from_slice(&invalid);
+            assert!(matches!(decode(data), Err(DecodeError::DataIntegrityError(_))));
+        }
+    }
+
+    #[test]
+    fn fungible_strict_val() {
+        use amplify::num::{i1024, u1024};
+
+        for state in [FungibleState::ZERO, FungibleState::Bits64(15), FungibleState::MAX] {
+            let val = state.to_strict_val();
+            assert_eq!(val, StrictVal::num(state.as_u64()));
+            assert_eq!(FungibleState::try_from_strict_val(&val), Ok(state));
+        }
+        assert_eq!(
+            FungibleState::try_from_strict_val(&StrictVal::num(15i64)),
+            Ok(FungibleState::Bits64(15))
+        );
+        assert_eq!(
+            FungibleState::try_from_strict_val(&StrictVal::num(u1024::from(15u64))),
+            Ok(FungibleState::Bits64(15))
+        );
+        assert_eq!(
+            FungibleState::try_from_strict_val(&StrictVal::num(i1024::from(15i64))),
+            Ok(FungibleState::Bits64(15))
+        );
+
+        for val in [
+            StrictVal::num(u64::MAX as u128 + 1),
+            StrictVal::num(-1i64),
+            StrictVal::num(i1024::from(-1i64)),
+            StrictVal::num(u1024::from(u128::MAX)),
+        ] {
+            assert_eq!(
+                FungibleState::try_from_strict_val(&val),
+                Err(FungibleValError::OutOfRange(val.to_string()))
+            );
+        }
+        for val in [StrictVal::Unit, StrictVal::str("15"), StrictVal::bytes([15u8])] {
+            assert_eq!(
+                FungibleState::try_from_strict_val(&val),
+                Err(FungibleValError::NotInteger(val.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn fungible_future_version() {
+        use strict_encoding::{StrictReader, StrictWriter};
+
+        let decode = |data: Vec<u8>| {
+            FungibleState::strict_decode(&mut StrictReader::in_memory(data, usize::MAX))
+        };
+        let state = FungibleState::Bits64(15);
+        let data = state
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        assert_eq!(data[0], 8);
+        assert_eq!(decode(data), Ok(state));
+
+        // 128-bit value tagged as the strict types U128 primitive
+        let mut future = vec![16u8];
+        future.extend([0xFFu8; 16]);
+        let Err(DecodeError::DataIntegrityError(msg)) = decode(future.clone()) else {
+            panic!("future fungible state must be rejected");
+        };
+        assert!(msg.contains("future RGB version"));
+
+        future.extend([1u8; 32]);
+        assert!(matches!(
+            RevealedValue::strict_decode(&mut StrictReader::in_memory(future, usize::MAX)),
+            Err(DecodeError::DataIntegrityError(_))
+        ));
+    }
+
+    fn strict_roundtrip(value: &ConcealedValue) -> ConcealedValue {
+        use strict_encoding::{StrictReader, StrictWriter};
+
+        let data = value
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        ConcealedValue::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap()
+    }
+
+    #[test]
+    fn placeholder_truncated() {
+        use strict_encoding::{StrictReader, StrictWriter};
+
+        let proof = RangeProof::default();
+        let data = proof
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        assert_eq!(data.len(), 513);
+        let decode = |data: &[u8]| {
+            RangeProof::strict_decode(&mut StrictReader::in_memory(data.to_vec(), usize::MAX))
+        };
+        assert_eq!(decode(&data).unwrap(), proof);
+        for len in [1, 2, 512] {
+            assert_eq!(
+                decode(&data[..len]),
+                Err(DecodeError::DataIntegrityError(s!(
+                    "range proof placeholder must be exactly 512 bytes long"
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn concealed_from_parts() {
+        let revealed = RevealedValue::new(15, &mut thread_rng());
+        let commitment = PedersenCommitment::commit(&revealed);
+        let range_proof = RangeProof::default();
+        let concealed = ConcealedValue::from_parts(commitment, range_proof);
+        assert_eq!(concealed.commitment, commitment);
+        assert_eq!(concealed.range_proof, range_proof);
+        assert_eq!(strict_roundtrip(&concealed), concealed);
+        assert_eq!(concealed.verify_disclosure(&revealed), Ok(()));
+        assert_eq!(concealed.verify_range_proof(), Err(RangeProofError::BulletproofsAbsent));
+        assert!(!concealed.verify());
+    }
+
+    #[test]
+    fn concealed_bulletproof() {
+        let revealed = RevealedValue::new(15, &mut thread_rng());
+        let range_proof = RangeProof::Bulletproof(Array::from([0x42; BULLETPROOF_LEN]));
+        let concealed =
+            ConcealedValue::from_parts(PedersenCommitment::commit(&revealed), range_proof);
+        let decoded = strict_roundtrip(&concealed);
+        assert_eq!(decoded, concealed);
+        assert_eq!(decoded.range_proof, range_proof);
+        assert_eq!(decoded.verify_range_proof(), Err(RangeProofError::BulletproofsAbsent));
+        assert!(!decoded.verify());
+    }
+
+    #[test]
+    fn range_proof_cache() {
+        let mut cache = RangeProofCache::new(2);
+        let concealed = |value: u64| {
+            ConcealedValue::from_parts(
+                PedersenCommitment::commit(&RevealedValue::new(value, &mut thread_rng())),
+                RangeProof::default(),
+            )
+        };
+        let value1 = concealed(1);
+        let value2 = concealed(2);
+        let value3 = concealed(3);
+
+        let res = value1.verify_range_proof();
+        assert_eq!(value1.verify_range_proof_cached(&mut cache), res);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert_eq!(value1.verify_range_proof_cached(&mut cache), res);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // Same commitment with different proof must not hit the cache
+        let other_proof = ConcealedValue::from_parts(
+            value1.commitment,
+            RangeProof::Placeholder(NoiseDumb::with_rng(&mut thread_rng())),
+        );
+        other_proof.verify_range_proof_cached(&mut cache).ok();
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        assert_eq!(cache.len(), 2);
+
+        // value1 was used before other_proof, so it gets evicted
+        value2.verify_range_proof_cached(&mut cache).ok();
+        value3.verify_range_proof_cached(&mut cache).ok();
+        assert_eq!(cache.len(), 2);
+        value1.verify_range_proof_cached(&mut cache).ok();
+        assert_eq!((cache.hits(), cache.misses()), (1, 5));
+        value3.verify_range_proof_cached(&mut cache).ok();
+        assert_eq!(cache.hits(), 2);
+
+        // Recently used entries survive a stream of new ones
+        let values = (0..8).map(concealed).collect::<Vec<_>>();
+        let mut cache = RangeProofCache::new(4);
+        for value in &values[..4] {
+            value.verify_range_proof_cached(&mut cache).ok();
+        }
+        values[0].verify_range_proof_cached(&mut cache).ok();
+        values[2].verify_range_proof_cached(&mut cache).ok();
+        values[4].verify_range_proof_cached(&mut cache).ok();
+        values[5].verify_range_proof_cached(&mut cache).ok();
+        assert_eq!(cache.len(), 4);
+        assert_eq!((cache.hits(), cache.misses()), (2, 6));
+        for value in [&values[0], &values[2], &values[4], &values[5]] {
+            value.verify_range_proof_cached(&mut cache).ok();
+        }
+        assert_eq!((cache.hits(), cache.misses()), (6, 6));
+        values[1].verify_range_proof_cached(&mut cache).ok();
+        values[3].verify_range_proof_cached(&mut cache).ok();
+        assert_eq!((cache.hits(), cache.misses()), (6, 8));
+        assert_eq!(cache.len(), 4);
+
+        let mut disabled = RangeProofCache::new(0);
+        value1.verify_range_proof_cached(&mut disabled).ok();
+        value1.verify_range_proof_cached(&mut disabled).ok();
+        assert!(disabled.is_empty());
+        assert_eq!(disabled.hits(), 0);
+    }
+
+    #[test]
+    fn disclosure_verification_many() {
+        for value in [0u64, 1, 15, u64::MAX] {
+            let revealed = RevealedValue::new(value, &mut thread_rng());
+            let concealed = ConcealedValue::from_parts(
+                PedersenCommitment::commit(&revealed),
+                RangeProof::default(),
+            );
+            assert!(concealed.verify_disclosure(&revealed).is_ok());
+            let other = RevealedValue::new(value, &mut thread_rng());
+            assert!(concealed.verify_disclosure(&other).is_err());
+        }
+    }
+
+    #[test]
+    fn disclosure_verification() {
+        let revealed = RevealedValue::new(15, &mut thread_rng());
+        let concealed = ConcealedValue {
+            commitment: PedersenCommitment::commit(&revealed),
+            range_proof: RangeProof::default(),
+        };
+        assert_eq!(concealed.verify_disclosure(&revealed), Ok(()));
+
+        let wrong_value = RevealedValue::with(16, revealed.blinding);
+        assert_eq!(
+            concealed.verify_disclosure(&wrong_value),
+            Err(RangeProofError::InvalidBlinding(revealed.blinding))
+        );
+
+        let wrong_blinding = RevealedValue::new(15, &mut thread_rng());
+        assert_eq!(
+            concealed.verify_disclosure(&wrong_blinding),
+            Err(RangeProofError::InvalidBlinding(wrong_blinding.blinding))
+        );
+    }
 }
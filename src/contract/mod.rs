@@ -35,7 +35,11 @@ pub use assignments::{
     Assign, AssignAttach, AssignData, AssignFungible, AssignRights, Assignments, AssignmentsRef,
     TypedAssigns,
 };
-pub use attachment::{AttachId, ConcealedAttach, RevealedAttach};
+pub use attachment::{
+    is_potentially_dangerous, sniff_matches, AttachId, AttachIdParseError, AttachmentHeader,
+    AttachmentProtocol, ConcealedAttach, RevealedAttach, Salt, ATTACHMENT_COMMITMENT_TAG,
+    DANGEROUS_MEDIA_TYPES,
+};
 pub use bundle::{BundleId, BundleItem, TransitionBundle};
 pub use contract::{
     AttachOutput, ContractHistory, ContractState, DataOutput, FungibleOutput, GlobalOrd, Opout,
@@ -43,13 +47,22 @@ pub use contract::{
 };
 pub use data::{ConcealedData, RevealedData, VoidState};
 pub use fungible::{
-    BlindingFactor, ConcealedValue, FieldOrderOverflow, FungibleState, NoiseDumb,
-    PedersenCommitment, RangeProof, RangeProofError, RevealedValue,
+    asset_generator, asset_generator_tag, contract_asset_generator, contract_generator_tag,
+    decode_values, encode_values, AmountError, AtomicAmount, BlindingFactor, ConcealedValue,
+    Disclosure, DisclosureError, DisplayAmount, FieldOrderOverflow, FungibleConversionError,
+    FungibleLenError, FungibleState, FungibleValError, InvalidUncompressedPoint, MergeError,
+    NoiseDumb, PedersenCommitment, RangeProof, RangeProofCache, RangeProofError, ReblindError,
+    RevealedValue, RevealedValueBuilder, SplitError, ValueBuildError, BLINDING_DERIVATION_TAG,
+    BULLETPROOF_LEN, CONTRACT_GENERATOR_TAG, PEDERSEN_COMMITMENT_LEN,
 };
 pub use global::{GlobalState, GlobalValues};
 pub use operations::{
-    ContractId, Extension, Genesis, Input, Inputs, OpId, OpRef, Operation, Redeemed, Transition,
-    Valencies,
+    infer_schema_requirements, operation_commitment_data, operation_state_commitments, ContractId,
+    Extension, Genesis, Input, Inputs, OpCommitmentData, OpId, OpRef, Operation, Redeemed,
+    Transition, Valencies,
 };
 pub use seal::{ExposedSeal, GenesisSeal, GraphSeal, SealWitness, SecretSeal, TxoSeal};
-pub use state::{ConfidentialState, ExposedState, StateCommitment, StateData, StateType};
+pub use state::{
+    conceal_all, ConfidentialState, ExposedState, OperationStateCommit, StateCommitment, StateData,
+    StateType,
+};
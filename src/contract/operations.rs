@@ -21,7 +21,7 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
-use std::collections::{btree_map, btree_set};
+use std::collections::{btree_map, btree_set, BTreeMap};
 use std::iter;
 use std::str::FromStr;
 
@@ -30,13 +30,13 @@ use amplify::hex::{FromHex, ToHex};
 use amplify::{hex, Bytes32, RawArray, Wrapper};
 use baid58::{Baid58ParseError, FromBaid58, ToBaid58};
 use bp::Chain;
-use commit_verify::{mpc, CommitmentId, Conceal};
+use commit_verify::{mpc, CommitEncode, CommitmentId, Conceal};
 use strict_encoding::{StrictDeserialize, StrictEncode, StrictSerialize};
 
 use crate::schema::{self, ExtensionType, OpFullType, OpType, SchemaId, TransitionType};
 use crate::{
     AssignmentType, Assignments, AssignmentsRef, Ffv, GenesisSeal, GlobalState, GraphSeal, Opout,
-    ReservedByte, TypedAssigns, LIB_NAME_RGB,
+    ReservedByte, StateCommitment, StateType, TypedAssigns, LIB_NAME_RGB,
 };
 
 #[derive(Wrapper, WrapperMut, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default, From)]
@@ -256,6 +256,87 @@ pub trait Operation {
     fn inputs(&self) -> Inputs;
 }
 
+/// Collects commitments to the owned state of all operation assignments.
+///
+/// The commitments are grouped by the assignment type, ordered by the type
+/// id, and within each type follow the order of assignments in the operation.
+/// Revealed state is concealed, so the result is the same for revealed and
+/// concealed forms of the operation.
+pub fn operation_state_commitments(
+    op: &(impl Operation + ?Sized),
+) -> BTreeMap<AssignmentType, Vec<StateCommitment>> {
+    let assignments = op.assignments();
+    assignments
+        .types()
+        .into_iter()
+        .filter_map(|ty| assignments.get(ty).map(|a| (ty, a.state_commitments())))
+        .collect()
+}
+
+/// Infers state types of all owned state assignment types used by the
+/// operation, i.e. the minimal set of owned state declarations a schema must
+/// have for the operation to pass validation.
+///
+/// The state type is defined both for revealed and confidential state, so the
+/// result is the same for the revealed and concealed forms of the operation.
+pub fn infer_schema_requirements(
+    op: &(impl Operation + ?Sized),
+) -> BTreeMap<AssignmentType, StateType> {
+    match op.assignments() {
+        AssignmentsRef::Genesis(assignments) => assignments
+            .iter()
+            .map(|(ty, assigns)| (*ty, assigns.state_type()))
+            .collect(),
+        AssignmentsRef::Graph(assignments) => assignments
+            .iter()
+            .map(|(ty, assigns)| (*ty, assigns.state_type()))
+            .collect(),
+    }
+}
+
+/// Data committed to by the operation [`OpId`] (or [`ContractId`] for
+/// genesis), together with the commitments of the individual operation state
+/// sections, as returned by [`operation_commitment_data`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OpCommitmentData {
+    /// Global state in the form it is committed to, i.e. revealed and
+    /// strict-encoded.
+    pub global: Vec<u8>,
+    /// Commitments to the owned state, see [`operation_state_commitments`].
+    pub owned: BTreeMap<AssignmentType, Vec<StateCommitment>>,
+    /// Metadata in the form it is committed to, i.e. strict-encoded.
+    pub metadata: Vec<u8>,
+    /// Full preimage hashed with SHA256 tagged with the operation type
+    /// [`CommitmentId::TAG`] to produce the operation id.
+    pub preimage: Vec<u8>,
+}
+
+/// Returns data committed to by the operation [`OpId`] (or [`ContractId`] for
+/// genesis), which are hashed with SHA256 tagged with the operation type
+/// [`CommitmentId::TAG`], together with the commitments of the global state,
+/// owned state and metadata sections allowing tools to audit the preimage.
+///
+/// The preimage consists of the operation fields serialized in the order of
+/// their declaration. Owned state and seals are concealed, with assignments
+/// ordered by the assignment type and following their order in the
+/// operation within each type; global state and metadata are committed in
+/// revealed form. Thus, the data are the same for the revealed and concealed
+/// forms of the operation.
+pub fn operation_commitment_data<Op: Operation + CommitEncode>(op: &Op) -> OpCommitmentData {
+    let mut global = Vec::new();
+    op.globals().commit_encode(&mut global);
+    let mut metadata = Vec::new();
+    op.metadata().commit_encode(&mut metadata);
+    let mut preimage = Vec::new();
+    op.commit_encode(&mut preimage);
+    OpCommitmentData {
+        global,
+        owned: operation_state_commitments(op),
+        metadata,
+        preimage,
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB)]
@@ -630,3 +711,199 @@ impl<'op> Operation for OpRef<'op> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::SmallVec;
+    use bp::secp256k1::rand::thread_rng;
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::{
+        AssignAttach, AssignFungible, AttachId, GlobalStateType, MediaType, RevealedAttach,
+        RevealedData, RevealedValue,
+    };
+
+    #[test]
+    fn commitment_data() {
+        use amplify::hex::ToHex;
+        use commit_verify::{DigestExt, Sha256};
+
+        use crate::{BlindingFactor, Salt};
+
+        let seal = GenesisSeal::strict_dumb();
+        let value = RevealedValue::with(5u64, BlindingFactor::try_from([1u8; 32]).unwrap());
+        let attach =
+            RevealedAttach::with_salt(AttachId::strict_dumb(), MediaType::Any, Salt::from(7u64));
+        let mut genesis = Genesis::strict_dumb();
+        genesis
+            .assignments
+            .insert(
+                AssignmentType::with(1),
+                TypedAssigns::Attachment(
+                    SmallVec::try_from_iter([AssignAttach::revealed(seal, attach)]).unwrap(),
+                ),
+            )
+            .unwrap();
+
+        genesis
+            .globals
+            .add_state(GlobalStateType::with(3), RevealedData::from(small_vec![0xAB, 0xCD]))
+            .unwrap();
+        genesis.metadata = small_vec![0xEF];
+
+        let attach_only = genesis.clone();
+        genesis
+            .assignments
+            .insert(
+                AssignmentType::with(2),
+                TypedAssigns::Fungible(
+                    SmallVec::try_from_iter([AssignFungible::revealed(seal, value)]).unwrap(),
+                ),
+            )
+            .unwrap();
+        assert_eq!(
+            operation_commitment_data(&attach_only),
+            operation_commitment_data(&attach_only.conceal())
+        );
+
+        let data = operation_commitment_data(&genesis);
+        let mut engine = Sha256::from_tag(Genesis::TAG);
+        engine.input_raw(&data.preimage);
+        assert_eq!(ContractId::from(engine.finish()), genesis.contract_id());
+
+        assert_eq!(data.global.to_hex(), "01030001000200abcd");
+        assert_eq!(data.metadata.to_hex(), "0100ef");
+        assert_eq!(data.owned, operation_state_commitments(&genesis));
+        let owned = data
+            .owned
+            .iter()
+            .map(|(ty, commitments)| {
+                let commitments = commitments
+                    .iter()
+                    .map(|c| c.commitment_bytes().to_hex())
+                    .collect::<Vec<_>>();
+                (ty.to_inner(), commitments)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(owned, vec![
+            (1, vec![s!("ff96d2c52fc1658c800dd6ed3662360be955b0ef458922527bf8f9cd4b5872be38")]),
+            (2, vec![s!("01099287ca0c392ef0a63533fe1ea7512555411e0cca4ba0c46f7686110cf077c13d")]),
+        ]);
+        assert_eq!(
+            data.preimage.to_hex(),
+            concat!(
+                // ffv, schema id and chain
+                "0000000000000000000000000000000000000000000000000000000000000000000000",
+                // metadata
+                "0100ef",
+                // global state
+                "01030001000200abcd",
+                // concealed assignments and valencies
+                "02010011d100143f0e41b006adbdec4a9c8b757216ecd6fc96f3692017e0bcee422be302002ded",
+                "1222c5a73a00ef75b0250bfa74e7f581f40b7964e7b7d63271f48f00821400"
+            )
+        );
+    }
+
+    #[test]
+    fn state_commitments_order() {
+        let seal = GenesisSeal::strict_dumb();
+        let fungible = TypedAssigns::Fungible(
+            SmallVec::try_from_iter([
+                AssignFungible::revealed(seal, RevealedValue::new(5u64, &mut thread_rng())),
+                AssignFungible::revealed(seal, RevealedValue::new(6u64, &mut thread_rng())),
+            ])
+            .unwrap(),
+        );
+        let attach = TypedAssigns::Attachment(
+            SmallVec::try_from_iter([AssignAttach::revealed(
+                seal,
+                RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any),
+            )])
+            .unwrap(),
+        );
+        let fungible_ty = AssignmentType::with(2);
+        let attach_ty = AssignmentType::with(1);
+
+        let mut genesis1 = Genesis::strict_dumb();
+        genesis1
+            .assignments
+            .insert(fungible_ty, fungible.clone())
+            .unwrap();
+        genesis1
+            .assignments
+            .insert(attach_ty, attach.clone())
+            .unwrap();
+        let mut genesis2 = Genesis::strict_dumb();
+        genesis2
+            .assignments
+            .insert(attach_ty, attach.clone())
+            .unwrap();
+        genesis2
+            .assignments
+            .insert(fungible_ty, fungible.clone())
+            .unwrap();
+
+        let commitments = operation_state_commitments(&genesis1);
+        assert_eq!(commitments.keys().copied().collect::<Vec<_>>(), vec![attach_ty, fungible_ty]);
+        assert_eq!(commitments[&fungible_ty], fungible.state_commitments());
+        assert_eq!(commitments[&fungible_ty].len(), 2);
+        assert_eq!(commitments[&attach_ty], attach.state_commitments());
+        assert_eq!(commitments, operation_state_commitments(&genesis1));
+        assert_eq!(commitments, operation_state_commitments(&genesis2));
+        assert!(operation_state_commitments(&Genesis::strict_dumb()).is_empty());
+    }
+
+    #[test]
+    fn schema_requirements() {
+        let seal = GraphSeal::strict_dumb();
+        let attach = RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any);
+        let fungible_ty = AssignmentType::with(2);
+        let attach_ty = AssignmentType::with(1);
+        let rights_ty = AssignmentType::with(3);
+
+        let mut transition = Transition::strict_dumb();
+        transition
+            .assignments
+            .insert(
+                fungible_ty,
+                TypedAssigns::Fungible(
+                    SmallVec::try_from_iter([AssignFungible::revealed(
+                        seal,
+                        RevealedValue::new(5u64, &mut thread_rng()),
+                    )])
+                    .unwrap(),
+                ),
+            )
+            .unwrap();
+        transition
+            .assignments
+            .insert(
+                attach_ty,
+                TypedAssigns::Attachment(
+                    SmallVec::try_from_iter([
+                        AssignAttach::revealed(seal, attach.clone()),
+                        AssignAttach::ConfidentialState {
+                            seal,
+                            state: attach.conceal(),
+                        },
+                    ])
+                    .unwrap(),
+                ),
+            )
+            .unwrap();
+        transition
+            .assignments
+            .insert(rights_ty, TypedAssigns::Declarative(none!()))
+            .unwrap();
+
+        let requirements = infer_schema_requirements(&transition);
+        assert_eq!(requirements, bmap! {
+            attach_ty => StateType::Attachment,
+            fungible_ty => StateType::Fungible,
+            rights_ty => StateType::Void,
+        });
+        assert!(infer_schema_requirements(&Genesis::strict_dumb()).is_empty());
+    }
+}
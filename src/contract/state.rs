@@ -20,15 +20,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use core::cmp::Ordering;
 use core::fmt::Debug;
 use core::hash::Hash;
 
-use commit_verify::{CommitEncode, Conceal};
-use strict_encoding::{StrictDecode, StrictDumb, StrictEncode};
+use commit_verify::{CommitEncode, Conceal, Digest, Sha256};
+use strict_encoding::{StrictDecode, StrictDumb, StrictEncode, StrictWriter};
 
 use crate::{
     ConcealedAttach, ConcealedData, ConcealedValue, RevealedAttach, RevealedData, RevealedValue,
-    LIB_NAME_RGB,
+    VoidState, LIB_NAME_RGB,
 };
 
 /// Marker trait for types of state which are just a commitment to the actual
@@ -55,6 +56,56 @@ pub trait ExposedState:
     type Confidential: ConfidentialState;
     fn state_type(&self) -> StateType;
     fn state_data(&self) -> StateData;
+
+    /// Returns commitment to the state, which is the same as the commitment of
+    /// the concealed state.
+    fn state_commitment(&self) -> StateCommitment { self.conceal().state_commitment() }
+}
+
+/// Owned state contributing to the operation commitment through the commitment
+/// to its concealed form.
+pub trait OperationStateCommit: ExposedState {
+    /// Returns the contribution of the state to the operation commitment, which
+    /// is the commitment encoding of the concealed state.
+    ///
+    /// The contribution is built from [`ExposedState::state_commitment`] and
+    /// not from [`Conceal::conceal`], so it is available for the fungible
+    /// state even without bulletproofs support.
+    fn commitment_contribution(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        match self.state_commitment() {
+            StateCommitment::Void => {}
+            StateCommitment::Fungible(value) => value.commit_encode(&mut bytes),
+            StateCommitment::Structured(data) => data.commit_encode(&mut bytes),
+            StateCommitment::Attachment(attach) => attach.commit_encode(&mut bytes),
+        }
+        bytes
+    }
+}
+
+impl OperationStateCommit for VoidState {}
+impl OperationStateCommit for RevealedValue {}
+impl OperationStateCommit for RevealedData {}
+impl OperationStateCommit for RevealedAttach {}
+
+/// Conceals each of the provided states, preserving their order, such that
+/// concealed states match their revealed counterparts by index.
+///
+/// ```
+/// # use amplify::Bytes32;
+/// # use commit_verify::Conceal;
+/// # use rgb::{conceal_all, AttachId, MediaType, RevealedAttach};
+/// let attachments = [
+///     RevealedAttach::new(AttachId::from(Bytes32::from([1u8; 32])), MediaType::Any),
+///     RevealedAttach::new(AttachId::from(Bytes32::from([2u8; 32])), MediaType::Any),
+/// ];
+/// let concealed = conceal_all(&attachments);
+/// assert_eq!(concealed.len(), 2);
+/// assert_eq!(concealed[0], attachments[0].conceal());
+/// assert_eq!(concealed[1], attachments[1].conceal());
+/// ```
+pub fn conceal_all<State: ExposedState>(states: &[State]) -> Vec<State::Confidential> {
+    states.iter().map(State::conceal).collect()
 }
 
 /// Categories of the state
@@ -80,6 +131,15 @@ pub enum StateType {
     Attachment,
 }
 
+impl StateType {
+    /// All state types, in the order of their declaration.
+    pub const ALL: &'static [StateType] =
+        &[StateType::Void, StateType::Fungible, StateType::Structured, StateType::Attachment];
+
+    /// Iterates over all state types, in the order of their declaration.
+    pub fn iter() -> impl Iterator<Item = StateType> { Self::ALL.iter().copied() }
+}
+
 /// Categories of the state
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
@@ -112,6 +172,14 @@ impl ExposedState for StateData {
         }
     }
     fn state_data(&self) -> StateData { self.clone() }
+    fn state_commitment(&self) -> StateCommitment {
+        match self {
+            StateData::Void => StateCommitment::Void,
+            StateData::Fungible(value) => value.state_commitment(),
+            StateData::Structured(data) => data.state_commitment(),
+            StateData::Attachment(attach) => attach.state_commitment(),
+        }
+    }
 }
 
 impl Conceal for StateData {
@@ -146,6 +214,54 @@ pub enum StateCommitment {
     Attachment(ConcealedAttach),
 }
 
+impl StateCommitment {
+    /// Constructs commitment to the void (declarative) state, which is the
+    /// same for its revealed and concealed forms.
+    pub const fn void() -> Self { StateCommitment::Void }
+
+    /// Returns canonical commitment-encoded bytes of the state commitment, as
+    /// they are used in the operation id preimage.
+    ///
+    /// The range proof of the fungible state is not a part of the commitment
+    /// and does not affect the returned bytes.
+    pub fn commitment_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.commit_encode(&mut bytes);
+        bytes
+    }
+}
+
+impl PartialOrd for StateCommitment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+/// Consensus-relevant total order over the state commitments, used to build
+/// canonical sorted lists of the commitments independently of the order in
+/// which they were added.
+///
+/// Commitments are compared by the SHA256 hash of their
+/// [`StateCommitment::commitment_bytes`]. Since the range proofs are not a part
+/// of the commitment bytes, commitments with equal hashes are further compared
+/// by their full strict encoding, keeping the order consistent with [`Eq`].
+impl Ord for StateCommitment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            return Ordering::Equal;
+        }
+        Sha256::digest(self.commitment_bytes())
+            .cmp(&Sha256::digest(other.commitment_bytes()))
+            .then_with(|| {
+                let serialize = |commitment: &StateCommitment| {
+                    commitment
+                        .strict_encode(StrictWriter::in_memory(usize::MAX))
+                        .expect("in-memory writer doesn't fail")
+                        .unbox()
+                };
+                serialize(self).cmp(&serialize(other))
+            })
+    }
+}
+
 impl ConfidentialState for StateCommitment {
     fn state_type(&self) -> StateType {
         match self {
@@ -157,3 +273,189 @@ impl ConfidentialState for StateCommitment {
     }
     fn state_commitment(&self) -> StateCommitment { *self }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn state_type_all() {
+        // Exhaustive match makes the test fail to compile when a new state
+        // type is added, reminding to update `StateType::ALL`.
+        fn index(ty: StateType) -> usize {
+            match ty {
+                StateType::Void => 0,
+                StateType::Fungible => 1,
+                StateType::Structured => 2,
+                StateType::Attachment => 3,
+            }
+        }
+        assert_eq!(StateType::ALL.len(), 4);
+        for (pos, ty) in StateType::iter().enumerate() {
+            assert_eq!(index(ty), pos);
+        }
+        assert!(StateType::ALL.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn void_commitment() {
+        use commit_verify::Conceal;
+        use strict_encoding::{StrictEncode, StrictWriter};
+
+        use crate::{AssignRights, GraphSeal, VoidState};
+
+        let void = StateCommitment::void();
+        assert_eq!(void, StateCommitment::Void);
+        assert_eq!(void.state_type(), StateType::Void);
+        assert_eq!(void.state_commitment(), void);
+
+        let state = VoidState::default();
+        assert_eq!(state.conceal(), state);
+        assert_eq!(ExposedState::state_commitment(&state), void);
+        assert_eq!(ConfidentialState::state_commitment(&state.conceal()), void);
+        assert_eq!(state.state_data(), StateData::Void);
+        assert_eq!(StateData::Void.conceal(), void);
+        assert_eq!(ExposedState::state_commitment(&StateData::Void), void);
+
+        let revealed = AssignRights::revealed(GraphSeal::strict_dumb(), state);
+        assert_eq!(revealed.state_commitment(), void);
+        assert_eq!(revealed.conceal().state_commitment(), void);
+
+        let data = void
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        assert_eq!(data, vec![0x00]);
+    }
+
+    #[test]
+    fn commitment_bytes() {
+        use amplify::hex::ToHex;
+        use commit_verify::Conceal;
+
+        use crate::{
+            AttachId, BlindingFactor, MediaType, RangeProof, RevealedAttach, RevealedValue, Salt,
+        };
+
+        assert_eq!(StateCommitment::Void.commitment_bytes(), vec![0x00]);
+
+        let blinding = BlindingFactor::try_from([0x11; 32]).unwrap();
+        let value = RevealedValue::with(5u64, blinding);
+        let fungible = ExposedState::state_commitment(&value);
+        assert_eq!(
+            fungible.commitment_bytes().to_hex(),
+            "0108f6179f71a88551e54de4808d07340d29b32cb675cc0f4c0b55e9db40e41b5301"
+        );
+
+        let StateCommitment::Fungible(concealed) = fungible else {
+            unreachable!()
+        };
+        let other_proof = StateCommitment::Fungible(ConcealedValue::from_parts(
+            concealed.commitment,
+            RangeProof::default(),
+        ));
+        assert_eq!(other_proof.commitment_bytes(), fungible.commitment_bytes());
+
+        let data = RevealedData::from(small_vec![0xde, 0xad, 0xbe, 0xef]);
+        let structured = StateCommitment::Structured(data.conceal());
+        assert_eq!(
+            structured.commitment_bytes().to_hex(),
+            "022b70787c52cdd23169bd46a5f31d7f23f8609b0d3b3a7a8a4f1109a594047b17"
+        );
+
+        let attach =
+            RevealedAttach::with_salt(AttachId::from([0x22; 32]), MediaType::Any, Salt::from(7));
+        let attachment = StateCommitment::Attachment(attach.conceal());
+        assert_eq!(
+            attachment.commitment_bytes().to_hex(),
+            "fff71d58c37fca266a9569106c526b0381759406299067aa1421005f019ac52f88"
+        );
+    }
+
+    #[test]
+    fn commitment_order() {
+        use bp::secp256k1::rand::seq::SliceRandom;
+        use bp::secp256k1::rand::thread_rng;
+
+        use crate::{
+            AttachId, BlindingFactor, MediaType, PedersenCommitment, RangeProof, RevealedAttach,
+            Salt,
+        };
+
+        let mut commitments = vec![StateCommitment::Void];
+        for seed in 1..=4u8 {
+            let commitment = PedersenCommitment::test_unique(seed);
+            commitments
+                .push(StateCommitment::Fungible(ConcealedValue::from_parts(commitment, none!())));
+            let data = RevealedData::from(small_vec![seed; 4]);
+            commitments.push(StateCommitment::Structured(data.conceal()));
+            let attach = RevealedAttach::with_salt(
+                AttachId::from([seed; 32]),
+                MediaType::Any,
+                Salt::from(seed as u64),
+            );
+            commitments.push(StateCommitment::Attachment(attach.conceal()));
+        }
+        let value = RevealedValue::with(1u64, BlindingFactor::public());
+        let fungible = ExposedState::state_commitment(&value);
+        let StateCommitment::Fungible(concealed) = fungible else {
+            unreachable!()
+        };
+        let other_proof = StateCommitment::Fungible(ConcealedValue::from_parts(
+            concealed.commitment,
+            RangeProof::default(),
+        ));
+        assert_ne!(fungible.cmp(&other_proof), Ordering::Equal);
+        commitments.extend([fungible, other_proof]);
+
+        let mut sorted = commitments.clone();
+        sorted.sort();
+        assert!(sorted.windows(2).all(|w| w[0] < w[1]));
+        for _ in 0..10 {
+            let mut shuffled = commitments.clone();
+            shuffled.shuffle(&mut thread_rng());
+            shuffled.sort();
+            assert_eq!(shuffled, sorted);
+        }
+    }
+
+    #[test]
+    fn commitment_contribution() {
+        use commit_verify::CommitVerify;
+
+        use crate::{
+            AttachId, BlindingFactor, MediaType, PedersenCommitment, RevealedAttach, Salt,
+        };
+
+        let commit_encoded = |state: &dyn Fn(&mut Vec<u8>)| {
+            let mut bytes = vec![];
+            state(&mut bytes);
+            bytes
+        };
+
+        let value = RevealedValue::with(5u64, BlindingFactor::try_from([0x11; 32]).unwrap());
+        let commitment = PedersenCommitment::commit(&value);
+        assert_eq!(
+            value.commitment_contribution(),
+            commit_encoded(&|e| commitment.commit_encode(e))
+        );
+        assert_eq!(value.commitment_contribution(), commit_encoded(&|e| value.commit_encode(e)));
+
+        let attach =
+            RevealedAttach::with_salt(AttachId::from([0x22; 32]), MediaType::Any, Salt::from(7));
+        assert_eq!(
+            attach.commitment_contribution(),
+            commit_encoded(&|e| attach.conceal().commit_encode(e))
+        );
+        assert_eq!(attach.commitment_contribution(), commit_encoded(&|e| attach.commit_encode(e)));
+
+        let data = RevealedData::from(small_vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            data.commitment_contribution(),
+            commit_encoded(&|e| data.conceal().commit_encode(e))
+        );
+        let void = VoidState::default();
+        assert!(void.commitment_contribution().is_empty());
+        assert_eq!(void.commitment_contribution(), commit_encoded(&|e| void.commit_encode(e)));
+    }
+}
@@ -0,0 +1,140 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2023 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2023 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Entry points for fuzzing strict decoders of RGB data types received from
+//! untrusted peers.
+//!
+//! Each function decodes a value from arbitrary bytes; the decoders must
+//! return an error on malformed input and never panic. Fuzz targets are
+//! expected to call these functions with the fuzzer-provided data.
+
+use strict_encoding::{DecodeError, StrictDecode, StrictReader};
+
+use crate::vm::AluScript;
+use crate::{ConcealedValue, RevealedAttach, RevealedValue};
+
+/// Decodes strict-encoded value of type `T` from the beginning of the `data`.
+pub fn decode<T: StrictDecode>(data: &[u8]) -> Result<T, DecodeError> {
+    T::strict_decode(&mut StrictReader::in_memory(data.to_vec(), data.len()))
+}
+
+/// Decodes revealed fungible state.
+pub fn decode_fungible(data: &[u8]) -> Result<RevealedValue, DecodeError> { decode(data) }
+
+/// Decodes concealed fungible state together with its range proof.
+pub fn decode_concealed_fungible(data: &[u8]) -> Result<ConcealedValue, DecodeError> {
+    decode(data)
+}
+
+/// Decodes revealed attachment.
+pub fn decode_attach(data: &[u8]) -> Result<RevealedAttach, DecodeError> { decode(data) }
+
+/// Decodes AluVM schema script.
+pub fn decode_script(data: &[u8]) -> Result<AluScript, DecodeError> { decode(data) }
+
+#[cfg(test)]
+mod test {
+    use aluvm::library::{Lib, LibSeg, LibSite};
+    use amplify::confinement::Confined;
+    use bp::secp256k1::rand::rngs::StdRng;
+    use bp::secp256k1::rand::{Rng, SeedableRng};
+    use commit_verify::CommitVerify;
+    use strict_encoding::{StrictEncode, StrictWriter};
+
+    use super::*;
+    use crate::vm::EntryPoint;
+    use crate::{AttachId, MediaType, NoiseDumb, PedersenCommitment, RangeProof};
+
+    fn encode(value: &impl StrictEncode) -> Vec<u8> {
+        value
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox()
+    }
+
+    /// Feeds the decoder with all truncations and single-byte mutations of
+    /// the valid encoding and with random data.
+    fn fuzz<T>(valid: Vec<u8>, decoder: fn(&[u8]) -> Result<T, DecodeError>) {
+        assert!(decoder(&valid).is_ok());
+        for len in 0..valid.len() {
+            assert!(decoder(&valid[..len]).is_err());
+        }
+        let mut rng = StdRng::seed_from_u64(0);
+        for pos in 0..valid.len() {
+            let mut data = valid.clone();
+            data[pos] ^= rng.gen_range(1..=u8::MAX);
+            let _ = decoder(&data);
+        }
+        for _ in 0..256 {
+            let len = rng.gen_range(0..valid.len() * 2);
+            let data = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+            let _ = decoder(&data);
+        }
+    }
+
+    fn script() -> AluScript {
+        let lib = Lib::with("ALU", vec![0x00; 4], vec![], LibSeg::default()).unwrap();
+        AluScript {
+            libs: Confined::try_from_iter([(lib.id(), lib.clone())]).unwrap(),
+            entry_points: Confined::try_from_iter([(
+                EntryPoint::ValidateGenesis,
+                LibSite::with(0, lib.id()),
+            )])
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn fuzz_decoders() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let revealed = RevealedValue::new(100_000u64, &mut rng);
+        fuzz(encode(&revealed), decode_fungible);
+        let concealed = ConcealedValue {
+            commitment: PedersenCommitment::commit(&revealed),
+            range_proof: RangeProof::Placeholder(NoiseDumb::with_rng(&mut rng)),
+        };
+        fuzz(encode(&concealed), decode_concealed_fungible);
+        let attach =
+            RevealedAttach::new_with_rng(AttachId::from([0x11; 32]), MediaType::Any, &mut rng);
+        fuzz(encode(&attach), decode_attach);
+        fuzz(encode(&script()), decode_script);
+    }
+
+    #[test]
+    fn known_bad() {
+        // zero blinding factor
+        let mut data = vec![0x08];
+        data.extend([0u8; 8 + 32]);
+        assert!(decode_fungible(&data).is_err());
+        // commitment not on the curve
+        let mut data = vec![0x08];
+        data.extend([0xFFu8; 32]);
+        data.push(0xFF);
+        data.extend([0u8; 512]);
+        assert!(decode_concealed_fungible(&data).is_err());
+        // unknown media type
+        let mut data = vec![0x11; 32];
+        data.push(0x02);
+        data.extend([0u8; 8]);
+        assert!(decode_attach(&data).is_err());
+    }
+}
@@ -50,6 +50,10 @@ pub mod validation;
 pub mod vm;
 #[cfg(feature = "stl")]
 pub mod stl;
+#[cfg(feature = "test-vectors")]
+pub mod vectors;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 
 pub mod prelude {
     pub use bp::dbc::{Anchor, AnchorId};
@@ -65,6 +69,11 @@ pub use prelude::*;
 
 pub const LIB_NAME_RGB: &str = "RGB";
 
+/// Version of the strict-encoded RGB data supported by this library. Decoders
+/// reject data marked with other fast-forward versions and data using
+/// encodings (like union tags) introduced by future versions.
+pub const RGB_STRICT_VERSION: Ffv = Ffv(0);
+
 /// Reserved byte.
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default, Debug, Display)]
 #[display("reserved")]
@@ -115,12 +124,12 @@ pub struct Ffv(u16);
 mod _ffv {
     use strict_encoding::{DecodeError, ReadTuple, StrictDecode, TypedRead};
 
-    use crate::Ffv;
+    use crate::{Ffv, RGB_STRICT_VERSION};
 
     impl StrictDecode for Ffv {
         fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
             let ffv = reader.read_tuple(|r| r.read_field().map(Self))?;
-            if ffv != Ffv::default() {
+            if ffv != RGB_STRICT_VERSION {
                 Err(DecodeError::DataIntegrityError(format!(
                     "unsupported fast-forward version code belonging to a future RGB version. \
                      Please update your software, or, if the problem persists, contact your \
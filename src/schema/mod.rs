@@ -37,4 +37,6 @@ pub use schema::{
     SubSchema, TransitionType, BLANK_TRANSITION_ID,
 };
 pub use script::{Script, VmType};
-pub use state::{FungibleType, GlobalStateSchema, MediaType, StateSchema};
+pub use state::{
+    FungibleType, GlobalStateSchema, InvalidMediaType, MediaType, RawMediaType, StateSchema,
+};
@@ -21,13 +21,33 @@
 // limitations under the License.
 
 use amplify::confinement::{TinyOrdMap, TinyOrdSet};
+use amplify::Wrapper;
 use strict_types::SemId;
 
 use super::{ExtensionType, GlobalStateType, Occurrences, TransitionType};
 use crate::LIB_NAME_RGB;
 
+/// Type of owned state assignments.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Display, From)]
+#[wrapper(FromStr, LowerHex, UpperHex)]
+#[display(inner)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[derive(CommitEncode)]
+#[commit_encode(strategy = strict)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct AssignmentType(#[from] u16);
+
+impl AssignmentType {
+    /// Constructs assignment type from its numeric value.
+    pub const fn with(ty: u16) -> Self { Self(ty) }
+}
+
 // Here we can use usize since encoding/decoding makes sure that it's u16
-pub type AssignmentType = u16;
 pub type ValencyType = u16;
 pub type GlobalSchema = TinyOrdMap<GlobalStateType, Occurrences>;
 pub type ValencySchema = TinyOrdSet<ValencyType>;
@@ -88,8 +108,8 @@ impl OpFullType {
     pub fn subtype(self) -> u16 {
         match self {
             OpFullType::Genesis => 0,
-            OpFullType::StateTransition(ty) => ty,
-            OpFullType::StateExtension(ty) => ty,
+            OpFullType::StateTransition(ty) => ty.into_inner(),
+            OpFullType::StateExtension(ty) => ty.into_inner(),
         }
     }
 
@@ -21,6 +21,7 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::str::FromStr;
 
 use amplify::confinement::{TinyOrdMap, TinyOrdSet};
@@ -34,18 +35,79 @@ use super::{
     AssignmentType, ExtensionSchema, GenesisSchema, Script, StateSchema, TransitionSchema,
     ValencyType,
 };
-use crate::{Ffv, GlobalStateSchema, Occurrences, LIB_NAME_RGB};
+use crate::{Ffv, GlobalStateSchema, MediaType, Occurrences, LIB_NAME_RGB};
 
 pub trait SchemaTypeIndex:
     Copy + Eq + Ord + Default + StrictType + StrictEncode + StrictDecode
 {
 }
 impl SchemaTypeIndex for u16 {}
+impl SchemaTypeIndex for GlobalStateType {}
+impl SchemaTypeIndex for ExtensionType {}
+impl SchemaTypeIndex for TransitionType {}
+impl SchemaTypeIndex for AssignmentType {}
 
-pub type GlobalStateType = u16;
-pub type ExtensionType = u16;
-pub type TransitionType = u16;
-pub const BLANK_TRANSITION_ID: u16 = TransitionType::MAX;
+/// Type of global state.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Display, From)]
+#[wrapper(FromStr, LowerHex, UpperHex)]
+#[display(inner)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[derive(CommitEncode)]
+#[commit_encode(strategy = strict)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct GlobalStateType(#[from] u16);
+
+impl GlobalStateType {
+    /// Constructs global state type from its numeric value.
+    pub const fn with(ty: u16) -> Self { Self(ty) }
+}
+
+/// Type of state extension operation.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Display, From)]
+#[wrapper(FromStr, LowerHex, UpperHex)]
+#[display(inner)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[derive(CommitEncode)]
+#[commit_encode(strategy = strict)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct ExtensionType(#[from] u16);
+
+impl ExtensionType {
+    /// Constructs extension type from its numeric value.
+    pub const fn with(ty: u16) -> Self { Self(ty) }
+}
+
+/// Type of state transition operation.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Display, From)]
+#[wrapper(FromStr, LowerHex, UpperHex)]
+#[display(inner)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[derive(CommitEncode)]
+#[commit_encode(strategy = strict)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
+pub struct TransitionType(#[from] u16);
+
+impl TransitionType {
+    /// Constructs transition type from its numeric value.
+    pub const fn with(ty: u16) -> Self { Self(ty) }
+}
+
+pub const BLANK_TRANSITION_ID: TransitionType = TransitionType::with(u16::MAX);
 
 /// Schema identifier.
 ///
@@ -152,13 +214,73 @@ impl<Root: SchemaRoot> Schema<Root> {
         }
         schema
     }
+
+    /// Lists media types accepted by any of the attachment assignment types
+    /// of the schema, without repetitions and in the media type order.
+    pub fn accepted_media_types(&self) -> Vec<MediaType> {
+        let mut media_types = BTreeSet::new();
+        for state_schema in self.owned_types.values() {
+            match state_schema {
+                StateSchema::Attachment(media_type) => {
+                    media_types.insert(media_type.clone());
+                }
+                StateSchema::AttachmentAnyOf(allowed) => {
+                    media_types.extend(allowed.iter().cloned())
+                }
+                StateSchema::Declarative |
+                StateSchema::Fungible(_) |
+                StateSchema::FungiblePublic(_) |
+                StateSchema::Structured(_) => {}
+            }
+        }
+        media_types.into_iter().collect()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use strict_encoding::StrictDumb;
+    use strict_types::SemId;
 
     use super::*;
+    use crate::SubSchema;
+
+    #[test]
+    fn accepted_media_types() {
+        let schema = SubSchema {
+            owned_types: tiny_bmap! {
+                AssignmentType::with(1) => StateSchema::Declarative,
+                AssignmentType::with(2) => StateSchema::Attachment(MediaType::ImagePng),
+                AssignmentType::with(3) => StateSchema::AttachmentAnyOf(tiny_bset![
+                    MediaType::ApplicationPdf,
+                    MediaType::ImagePng,
+                    MediaType::ImageJpeg
+                ]),
+                AssignmentType::with(4) => StateSchema::Attachment(MediaType::TextPlain),
+                AssignmentType::with(5) => StateSchema::Structured(SemId::strict_dumb()),
+            },
+            ..default!()
+        };
+        let accepted = schema.accepted_media_types();
+        assert_eq!(accepted, vec![
+            MediaType::TextPlain,
+            MediaType::ImagePng,
+            MediaType::ImageJpeg,
+            MediaType::ApplicationPdf
+        ]);
+        for media_type in &accepted {
+            assert!(schema
+                .owned_types
+                .values()
+                .any(|state_schema| state_schema.accepts_media_type(media_type)));
+        }
+
+        let no_attachments = SubSchema {
+            owned_types: tiny_bmap! { AssignmentType::with(1) => StateSchema::Declarative },
+            ..default!()
+        };
+        assert!(no_attachments.accepted_media_types().is_empty());
+    }
 
     #[test]
     fn display() {
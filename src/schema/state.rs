@@ -20,37 +20,290 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::confinement::{self, TinyOrdMap, TinyOrdSet, TinyString};
 use strict_encoding::constants::U64;
 use strict_types::SemId;
 
 use crate::{StateType, LIB_NAME_RGB};
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+/// Media type of an attachment.
+///
+/// Registered media types are encoded with a single-byte tag; other types
+/// are kept as [`MediaType::Raw`] exactly as they were constructed, since the
+/// library does not enforce a MIME standard.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
-#[strict_type(lib = LIB_NAME_RGB, tags = repr, into_u8, try_from_u8)]
+#[strict_type(lib = LIB_NAME_RGB, tags = custom, dumb = Self::Any)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
     serde(crate = "serde_crate", rename_all = "camelCase")
 )]
 #[non_exhaustive]
-#[repr(u8)]
 pub enum MediaType {
+    #[display("text/plain")]
+    #[strict_type(tag = 0x01)]
+    TextPlain,
+
+    #[display("image/png")]
+    #[strict_type(tag = 0x10)]
+    ImagePng,
+    #[display("image/jpeg")]
+    #[strict_type(tag = 0x11)]
+    ImageJpeg,
+    #[display("image/gif")]
+    #[strict_type(tag = 0x12)]
+    ImageGif,
+
+    #[display("application/octet-stream")]
+    #[strict_type(tag = 0x20)]
+    ApplicationOctetStream,
+    #[display("application/pdf")]
+    #[strict_type(tag = 0x21)]
+    ApplicationPdf,
+    #[display("application/javascript")]
+    #[strict_type(tag = 0x22)]
+    ApplicationJavascript,
+    #[display("application/x-sh")]
+    #[strict_type(tag = 0x23)]
+    ApplicationXSh,
+    #[display("application/x-msdownload")]
+    #[strict_type(tag = 0x24)]
+    ApplicationXMsdownload,
+
+    /// Media type which is not registered in this library, kept as provided.
+    #[display(inner)]
+    #[strict_type(tag = 0xFE)]
+    Raw(RawMediaType),
+
     #[display("*/*")]
-    #[strict_type(dumb)]
-    Any = 0xFF,
-    // TODO: Complete MIME type implementation
+    #[strict_type(tag = 0xFF)]
+    Any,
 }
 
 impl MediaType {
+    /// All registered media types known to this version of the library.
+    pub const ALL: [MediaType; 10] = [
+        MediaType::TextPlain,
+        MediaType::ImagePng,
+        MediaType::ImageJpeg,
+        MediaType::ImageGif,
+        MediaType::ApplicationOctetStream,
+        MediaType::ApplicationPdf,
+        MediaType::ApplicationJavascript,
+        MediaType::ApplicationXSh,
+        MediaType::ApplicationXMsdownload,
+        MediaType::Any,
+    ];
+
+    /// Constructs media type from the raw type and subtype, which are kept
+    /// exactly as provided, without canonicalization.
+    ///
+    /// # Errors
+    ///
+    /// If the type or subtype is longer than 255 bytes.
+    pub fn raw(
+        ty: impl AsRef<str>,
+        subtype: impl AsRef<str>,
+    ) -> Result<MediaType, confinement::Error> {
+        Ok(MediaType::Raw(RawMediaType {
+            ty: TinyString::try_from(ty.as_ref().to_owned())?,
+            subtype: TinyString::try_from(subtype.as_ref().to_owned())?,
+            params: empty!(),
+        }))
+    }
+
+    /// Adds parameter, like the `charset` in `text/plain; charset=utf-8`, to
+    /// the media type, replacing the previous value of a parameter with the
+    /// same name. Registered media types are converted into
+    /// [`MediaType::Raw`], since only raw media types carry parameters.
+    ///
+    /// The name and value are kept as provided; use [`MediaType::canonical`]
+    /// to normalize them.
+    ///
+    /// # Errors
+    ///
+    /// If the name or value is longer than 255 bytes, or if the media type
+    /// already has 255 parameters.
+    pub fn with_param(
+        self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<MediaType, confinement::Error> {
+        let mut raw = match self {
+            MediaType::Raw(raw) => raw,
+            registered => {
+                let essence = registered.to_string();
+                let (ty, subtype) = essence
+                    .split_once('/')
+                    .expect("registered media types have a subtype");
+                RawMediaType {
+                    ty: TinyString::try_from(ty.to_owned())?,
+                    subtype: TinyString::try_from(subtype.to_owned())?,
+                    params: empty!(),
+                }
+            }
+        };
+        raw.params.insert(
+            TinyString::try_from(name.as_ref().to_owned())?,
+            TinyString::try_from(value.as_ref().to_owned())?,
+        )?;
+        Ok(MediaType::Raw(raw))
+    }
+
+    /// Returns the `type/subtype` part of the media type, without parameters.
+    pub fn essence(&self) -> String {
+        match self {
+            MediaType::Raw(raw) => format!("{}/{}", raw.ty, raw.subtype),
+            registered => registered.to_string(),
+        }
+    }
+
+    /// Returns value of the parameter with the given name, if the media type
+    /// has it.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        let MediaType::Raw(raw) = self else {
+            return None;
+        };
+        raw.params
+            .iter()
+            .find(|(key, _)| key.as_str() == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Checks whether attachment of `self` media type conforms to the media
+    /// type `other` required by a schema, comparing their canonical forms:
+    /// either the types are the same or `other` is the `*/*` wildcard, and
+    /// all parameters of `other` are present in `self` with the same values.
+    /// The attachment may have additional parameters.
     pub fn conforms(&self, other: &MediaType) -> bool {
-        match (self, other) {
-            (MediaType::Any, MediaType::Any) => true,
+        let this = self.canonical();
+        let other = other.canonical();
+        let essence = other.essence();
+        (essence == MediaType::Any.essence() || essence == this.essence()) &&
+            match other {
+                MediaType::Raw(raw) => raw
+                    .params
+                    .iter()
+                    .all(|(name, value)| this.param(name) == Some(value.as_str())),
+                _ => true,
+            }
+    }
+
+    /// Returns canonical form of the media type, with the type, subtype and
+    /// parameter names lowercased and whitespace around them and around
+    /// parameter values trimmed.
+    ///
+    /// Raw media types without parameters matching one of the registered
+    /// types are converted into the registered variant; registered types are
+    /// canonical by construction.
+    pub fn canonical(&self) -> MediaType {
+        let MediaType::Raw(raw) = self else {
+            return self.clone();
+        };
+        let raw = raw.canonical();
+        if !raw.params.is_empty() {
+            return MediaType::Raw(raw);
         }
+        let essence = raw.to_string();
+        MediaType::ALL
+            .into_iter()
+            .find(|media_type| media_type.to_string() == essence)
+            .unwrap_or(MediaType::Raw(raw))
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// Media type not registered in this library, consisting of the type and
+/// subtype strings and an ordered set of parameters.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+pub struct RawMediaType {
+    pub ty: TinyString,
+    pub subtype: TinyString,
+    pub params: TinyOrdMap<TinyString, TinyString>,
+}
+
+impl RawMediaType {
+    /// Returns the media type with lowercased type, subtype and parameter
+    /// names and with trimmed whitespace around them and around parameter
+    /// values.
+    pub fn canonical(&self) -> RawMediaType {
+        fn trim(s: &TinyString) -> TinyString {
+            TinyString::try_from(s.trim().to_owned()).expect("trimming doesn't increase the length")
+        }
+        fn lowercase(s: &TinyString) -> TinyString {
+            TinyString::try_from(s.trim().to_ascii_lowercase())
+                .expect("canonicalization doesn't increase the length")
+        }
+        let params = self
+            .params
+            .iter()
+            .map(|(name, value)| (lowercase(name), trim(value)))
+            .collect::<BTreeMap<_, _>>();
+        RawMediaType {
+            ty: lowercase(&self.ty),
+            subtype: lowercase(&self.subtype),
+            params: TinyOrdMap::try_from(params)
+                .expect("canonicalization doesn't increase the number of parameters"),
+        }
+    }
+}
+
+impl Display for RawMediaType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.ty, self.subtype)?;
+        for (name, value) in &self.params {
+            write!(f, "; {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error parsing [`MediaType`] from a string.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+/// invalid media type '{0}'; media type must have a form of `type/subtype`
+/// with non-empty type and subtype, optionally followed by `; name=value`
+/// parameters, each not longer than 255 bytes.
+pub struct InvalidMediaType(pub String);
+
+impl FromStr for MediaType {
+    type Err = InvalidMediaType;
+
+    /// Parses media type from its textual representation and returns its
+    /// [`MediaType::canonical`] form, ignoring the case of the type, subtype
+    /// and parameter names and whitespace around them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || InvalidMediaType(s.to_owned());
+        let mut parts = s.split(';');
+        let essence = parts.next().expect("split always returns one item");
+        let (ty, subtype) = essence.split_once('/').ok_or_else(err)?;
+        if ty.trim().is_empty() || subtype.trim().is_empty() {
+            return Err(err());
+        }
+        let mut media_type = MediaType::raw(ty, subtype).map_err(|_| err())?;
+        for param in parts {
+            let (name, value) = param.split_once('=').ok_or_else(err)?;
+            if name.trim().is_empty() {
+                return Err(err());
+            }
+            media_type = media_type.with_param(name, value).map_err(|_| err())?;
+        }
+        Ok(media_type.canonical())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB, tags = order)]
 #[cfg_attr(
@@ -64,15 +317,41 @@ pub enum StateSchema {
     Fungible(FungibleType),
     Structured(SemId),
     Attachment(MediaType),
+    /// Attachment of any of the listed media types.
+    AttachmentAnyOf(TinyOrdSet<MediaType>),
+    /// Fungible state which must be kept public: confidential amounts and
+    /// range proofs are not allowed.
+    FungiblePublic(FungibleType),
 }
 
 impl StateSchema {
+    /// Constructs fungible state schema allowing confidential amounts.
+    pub fn fungible(ty: FungibleType) -> Self { StateSchema::Fungible(ty) }
+
+    /// Constructs fungible state schema requiring all amounts to be public.
+    pub fn fungible_public(ty: FungibleType) -> Self { StateSchema::FungiblePublic(ty) }
+
     pub fn state_type(&self) -> StateType {
         match self {
             StateSchema::Declarative => StateType::Void,
-            StateSchema::Fungible(_) => StateType::Fungible,
+            StateSchema::Fungible(_) | StateSchema::FungiblePublic(_) => StateType::Fungible,
             StateSchema::Structured(_) => StateType::Structured,
-            StateSchema::Attachment(_) => StateType::Attachment,
+            StateSchema::Attachment(_) | StateSchema::AttachmentAnyOf(_) => StateType::Attachment,
+        }
+    }
+
+    /// Checks whether attachment of the given media type is allowed by the
+    /// schema. Always `false` for non-attachment schemas.
+    pub fn accepts_media_type(&self, media_type: &MediaType) -> bool {
+        match self {
+            StateSchema::Attachment(expected) => media_type.conforms(expected),
+            StateSchema::AttachmentAnyOf(allowed) => {
+                allowed.iter().any(|expected| media_type.conforms(expected))
+            }
+            StateSchema::Declarative |
+            StateSchema::Fungible(_) |
+            StateSchema::FungiblePublic(_) |
+            StateSchema::Structured(_) => false,
         }
     }
 }
@@ -99,6 +378,16 @@ pub enum FungibleType {
     Unsigned64Bit = U64.into_code(),
 }
 
+impl FungibleType {
+    /// Returns bit width of the values of this type, which must be covered
+    /// by the range proofs of the confidential state.
+    pub fn bits(self) -> u8 {
+        match self {
+            FungibleType::Unsigned64Bit => 64,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_RGB)]
@@ -127,3 +416,210 @@ impl GlobalStateSchema {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use strict_encoding::{StrictDecode, StrictEncode, StrictReader, StrictWriter};
+
+    use super::*;
+
+    #[test]
+    fn media_type_canonical() {
+        for media_type in MediaType::ALL {
+            assert_eq!(media_type.canonical(), media_type);
+            assert_eq!(MediaType::from_str(&media_type.to_string()), Ok(media_type.clone()));
+            let upper = media_type.to_string().to_uppercase();
+            assert_eq!(MediaType::from_str(&upper), Ok(media_type));
+        }
+        assert_eq!(MediaType::from_str("Image/PNG"), Ok(MediaType::ImagePng));
+        assert_eq!(MediaType::from_str(" image/png\n"), Ok(MediaType::ImagePng));
+        assert_eq!(MediaType::from_str("\tText / Plain "), Ok(MediaType::TextPlain));
+        assert_eq!(MediaType::from_str(" */* "), Ok(MediaType::Any));
+        assert_eq!(MediaType::from_str("* / *"), Ok(MediaType::Any));
+        assert_eq!(
+            MediaType::from_str(" Image/WebP "),
+            Ok(MediaType::raw("image", "webp").unwrap())
+        );
+        assert_eq!(MediaType::from_str("image"), Err(InvalidMediaType(s!("image"))));
+        assert_eq!(MediaType::from_str("*"), Err(InvalidMediaType(s!("*"))));
+        assert_eq!(MediaType::from_str("image/ "), Err(InvalidMediaType(s!("image/ "))));
+        let long = format!("image/{}", "x".repeat(256));
+        assert_eq!(MediaType::from_str(&long), Err(InvalidMediaType(long)));
+    }
+
+    #[test]
+    fn media_type_raw() {
+        let raw = MediaType::raw(" Image", "PNG ").unwrap();
+        assert_eq!(raw.to_string(), " Image/PNG ");
+        assert_ne!(raw, MediaType::ImagePng);
+        assert_eq!(raw.canonical(), MediaType::ImagePng);
+        assert_eq!(MediaType::raw("*", "*").unwrap().canonical(), MediaType::Any);
+
+        let nonstandard = MediaType::raw("X-Vendor", " Format").unwrap();
+        assert_eq!(nonstandard.canonical(), MediaType::raw("x-vendor", "format").unwrap());
+        assert_eq!(nonstandard.canonical().canonical(), nonstandard.canonical());
+        assert!(MediaType::raw("x".repeat(256), "format").is_err());
+    }
+
+    #[test]
+    fn media_type_params() {
+        let utf8 = MediaType::TextPlain.with_param("charset", "utf-8").unwrap();
+        let utf8_lang = utf8.clone().with_param("lang", "en").unwrap();
+        let latin = MediaType::TextPlain
+            .with_param("charset", "latin1")
+            .unwrap();
+
+        assert_eq!(utf8_lang.to_string(), "text/plain; charset=utf-8; lang=en");
+        assert_eq!(utf8_lang.essence(), "text/plain");
+        assert_eq!(utf8.param("charset"), Some("utf-8"));
+        assert_eq!(utf8.param("lang"), None);
+        assert_eq!(MediaType::TextPlain.param("charset"), None);
+        assert_eq!(utf8.canonical(), utf8);
+        assert_ne!(utf8.canonical(), MediaType::TextPlain);
+
+        assert_eq!(
+            MediaType::from_str("Text/Plain; Charset = utf-8 ;lang=en"),
+            Ok(utf8_lang.clone())
+        );
+        assert_eq!(
+            MediaType::from_str("text/plain; charset=UTF-8"),
+            Ok(MediaType::TextPlain.with_param("charset", "UTF-8").unwrap())
+        );
+        assert_eq!(MediaType::from_str("text/plain;"), Err(InvalidMediaType(s!("text/plain;"))));
+        assert_eq!(
+            MediaType::from_str("text/plain; =utf-8"),
+            Err(InvalidMediaType(s!("text/plain; =utf-8")))
+        );
+
+        assert!(utf8.conforms(&MediaType::TextPlain));
+        assert!(utf8.conforms(&MediaType::Any));
+        assert!(utf8.conforms(&utf8));
+        assert!(utf8_lang.conforms(&utf8));
+        assert!(!MediaType::TextPlain.conforms(&utf8));
+        assert!(!latin.conforms(&utf8));
+        assert!(!utf8.conforms(&utf8_lang));
+        assert!(!utf8.conforms(&MediaType::ImagePng));
+        let any_utf8 = MediaType::Any.with_param("charset", "utf-8").unwrap();
+        assert!(utf8.conforms(&any_utf8));
+        assert!(!latin.conforms(&any_utf8));
+        let raw = MediaType::raw("TEXT", "plain")
+            .unwrap()
+            .with_param("CharSet", " utf-8")
+            .unwrap();
+        assert!(raw.conforms(&utf8));
+        assert_eq!(raw.canonical(), utf8);
+    }
+
+    #[test]
+    fn media_type_conforms() {
+        let png = MediaType::from_str("Image/PNG").unwrap();
+        assert!(png.conforms(&MediaType::ImagePng));
+        assert!(png.conforms(&MediaType::Any));
+        assert!(MediaType::Any.conforms(&MediaType::Any));
+        assert!(!png.conforms(&MediaType::ImageJpeg));
+        assert!(!MediaType::Any.conforms(&MediaType::ImagePng));
+        assert!(MediaType::raw("IMAGE", "png")
+            .unwrap()
+            .conforms(&MediaType::ImagePng));
+        assert!(MediaType::ImagePng.conforms(&MediaType::raw("image", " PNG").unwrap()));
+        let webp = MediaType::raw("image", "webp").unwrap();
+        assert!(MediaType::raw("Image", "WebP").unwrap().conforms(&webp));
+        assert!(webp.conforms(&MediaType::raw("*", "*").unwrap()));
+        assert!(!webp.conforms(&MediaType::ImagePng));
+
+        let schema = StateSchema::AttachmentAnyOf(tiny_bset![
+            MediaType::ImagePng,
+            MediaType::ApplicationPdf
+        ]);
+        assert!(schema.accepts_media_type(&png));
+        assert!(schema.accepts_media_type(&MediaType::from_str("application/PDF").unwrap()));
+        assert!(!schema.accepts_media_type(&MediaType::ImageGif));
+        for schema in [
+            StateSchema::Declarative,
+            StateSchema::fungible(FungibleType::Unsigned64Bit),
+            StateSchema::fungible_public(FungibleType::Unsigned64Bit),
+            StateSchema::Structured(SemId::default()),
+        ] {
+            assert!(!schema.accepts_media_type(&png));
+            assert!(!schema.accepts_media_type(&MediaType::Any));
+        }
+    }
+
+    #[test]
+    fn fungible_schema_encoding() {
+        for (schema, data) in [
+            // the same layout as before public-only fungible state was added
+            (StateSchema::fungible(FungibleType::Unsigned64Bit), vec![0x01, U64.into_code()]),
+            (StateSchema::fungible_public(FungibleType::Unsigned64Bit), vec![
+                0x05,
+                U64.into_code(),
+            ]),
+        ] {
+            assert_eq!(schema.state_type(), StateType::Fungible);
+            let encoded = schema
+                .strict_encode(StrictWriter::in_memory(usize::MAX))
+                .unwrap()
+                .unbox();
+            assert_eq!(encoded, data);
+            let decoded =
+                StateSchema::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap();
+            assert_eq!(decoded, schema);
+        }
+    }
+
+    #[test]
+    fn media_type_encoding() {
+        for (media_type, tag) in [
+            (MediaType::TextPlain, 0x01u8),
+            (MediaType::ImagePng, 0x10),
+            (MediaType::ApplicationPdf, 0x21),
+            (MediaType::ApplicationXMsdownload, 0x24),
+            (MediaType::Any, 0xFF),
+        ] {
+            let data = media_type
+                .strict_encode(StrictWriter::in_memory(usize::MAX))
+                .unwrap()
+                .unbox();
+            assert_eq!(data, vec![tag]);
+            let decoded =
+                MediaType::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap();
+            assert_eq!(decoded, media_type);
+        }
+        assert!(
+            MediaType::strict_decode(&mut StrictReader::in_memory(vec![0x02], usize::MAX)).is_err()
+        );
+
+        let raw = MediaType::raw("Image", "WebP").unwrap();
+        let data = raw
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        assert_eq!(data, b"\xFE\x05Image\x04WebP\x00");
+        let decoded =
+            MediaType::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap();
+        assert_eq!(decoded, raw);
+
+        let params = MediaType::TextPlain.with_param("charset", "utf-8").unwrap();
+        let data = params
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        assert_eq!(data, b"\xFE\x04text\x05plain\x01\x07charset\x05utf-8");
+        let decoded =
+            MediaType::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap();
+        assert_eq!(decoded, params);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn media_type_serde() {
+        for media_type in [
+            MediaType::ImagePng,
+            MediaType::raw("Image", "WebP").unwrap(),
+            MediaType::TextPlain.with_param("charset", "utf-8").unwrap(),
+        ] {
+            let json = serde_json::to_string(&media_type).unwrap();
+            assert_eq!(serde_json::from_str::<MediaType>(&json).unwrap(), media_type);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2023 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2023 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use crate::validation::{ConsignmentApi, Status};
+use crate::{OpId, OpRef, Operation, Schema, SchemaId, SchemaRoot};
+
+/// Validation state for a growing contract history, caching results of the
+/// operation validation against the schema (including schema script
+/// execution), such that validating the history after adding an operation
+/// doesn't re-validate already validated operations.
+///
+/// # Cache invalidation
+///
+/// Results are keyed by the operation id, which commits to the operation
+/// data and the ids of its inputs, and are valid only for the schema the
+/// context was created with:
+/// - replacing the schema with a different one (having a different schema id)
+///   using [`ValidationContext::set_schema`] drops all cached results;
+/// - operations whose validation has produced failures are never cached, since
+///   the failures may be caused by data missing from the consignment (like
+///   absent parent operations), which may be provided later;
+/// - specific results can be dropped with [`ValidationContext::invalidate`], or
+///   all of them with [`ValidationContext::clear`].
+#[derive(Clone, Debug)]
+pub struct ValidationContext<Root: SchemaRoot> {
+    schema: Schema<Root>,
+    schema_id: SchemaId,
+    validated: BTreeMap<OpId, Status>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<Root: SchemaRoot> ValidationContext<Root> {
+    /// Constructs empty validation context for the schema.
+    pub fn new(schema: Schema<Root>) -> Self {
+        let schema_id = schema.schema_id();
+        ValidationContext {
+            schema,
+            schema_id,
+            validated: empty!(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns schema used for the validation.
+    pub fn schema(&self) -> &Schema<Root> { &self.schema }
+
+    /// Replaces the schema used for the validation, dropping all cached
+    /// results if the schema id has changed.
+    pub fn set_schema(&mut self, schema: Schema<Root>) {
+        let schema_id = schema.schema_id();
+        if schema_id != self.schema_id {
+            self.validated.clear();
+        }
+        self.schema = schema;
+        self.schema_id = schema_id;
+    }
+
+    /// Checks whether the operation validation result is cached.
+    pub fn is_validated(&self, opid: OpId) -> bool { self.validated.contains_key(&opid) }
+
+    /// Drops cached validation result for the operation, returning whether it
+    /// was present.
+    pub fn invalidate(&mut self, opid: OpId) -> bool { self.validated.remove(&opid).is_some() }
+
+    /// Drops all cached validation results.
+    pub fn clear(&mut self) { self.validated.clear() }
+
+    /// Number of cached validation results.
+    pub fn len(&self) -> usize { self.validated.len() }
+
+    /// Detects whether there are no cached validation results.
+    pub fn is_empty(&self) -> bool { self.validated.is_empty() }
+
+    /// Number of validations answered from the cache.
+    pub fn hits(&self) -> usize { self.hits }
+
+    /// Number of validations which had to be performed.
+    pub fn misses(&self) -> usize { self.misses }
+
+    /// Validates operation against the schema, unless it was already
+    /// successfully validated, in which case the cached status is returned.
+    ///
+    /// Validation of the operation parents is not performed; they have to be
+    /// validated with separate calls, which are cheap for the parents
+    /// validated before.
+    pub fn validate_incremental<C: ConsignmentApi>(
+        &mut self,
+        consignment: &C,
+        op: OpRef,
+    ) -> Status {
+        let opid = op.id();
+        if let Some(status) = self.validated.get(&opid) {
+            self.hits += 1;
+            return status.clone();
+        }
+        self.misses += 1;
+        let status = self.schema.validate(consignment, op, &self.schema.script);
+        if status.failures.is_empty() {
+            self.validated.insert(opid, status.clone());
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use amplify::confinement::SmallBlob;
+    use strict_encoding::StrictDumb;
+    use strict_types::typelib::{Dependency, LibBuilder};
+    use strict_types::typesys::SystemBuilder;
+
+    use super::*;
+    use crate::validation::AnchoredBundle;
+    use crate::{
+        BundleId, Extension, Genesis, SecretSeal, SubSchema, Transition, TransitionBundle,
+        TransitionType,
+    };
+
+    struct GenesisOnly {
+        schema: SubSchema,
+        genesis: Genesis,
+    }
+
+    impl ConsignmentApi for GenesisOnly {
+        type BundleIter<'container> = std::slice::Iter<'container, AnchoredBundle>;
+
+        fn schema(&self) -> &SubSchema { &self.schema }
+
+        fn operation(&self, opid: OpId) -> Option<OpRef<'_>> {
+            (opid == self.genesis.id()).then_some(OpRef::Genesis(&self.genesis))
+        }
+
+        fn genesis(&self) -> &Genesis { &self.genesis }
+
+        fn transition(&self, _opid: OpId) -> Option<&Transition> { None }
+
+        fn extension(&self, _opid: OpId) -> Option<&Extension> { None }
+
+        fn terminals(&self) -> BTreeSet<(BundleId, SecretSeal)> { none!() }
+
+        fn anchored_bundles(&self) -> Self::BundleIter<'_> { [].iter() }
+
+        fn bundle_by_id(&self, _bundle_id: BundleId) -> Option<&TransitionBundle> { None }
+
+        fn op_ids_except(&self, ids: &BTreeSet<OpId>) -> BTreeSet<OpId> {
+            bset![self.genesis.id()].difference(ids).copied().collect()
+        }
+
+        fn has_operation(&self, opid: OpId) -> bool { opid == self.genesis.id() }
+
+        fn known_transitions_by_bundle_id(&self, _bundle_id: BundleId) -> Option<Vec<&Transition>> {
+            None
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test")]
+    struct Meta(u8);
+
+    fn consignment(metadata: Vec<u8>) -> GenesisOnly {
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Meta>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let mut schema = SubSchema::default();
+        schema.genesis.metadata = *sys.resolve("Test.Meta").unwrap();
+        schema.type_system = sys.into_type_system();
+        let mut genesis = Genesis::strict_dumb();
+        genesis.metadata = SmallBlob::try_from(metadata).unwrap();
+        GenesisOnly { schema, genesis }
+    }
+
+    #[test]
+    fn incremental_cache() {
+        let consignment = consignment(vec![1]);
+        let genesis = OpRef::Genesis(&consignment.genesis);
+        let mut context = ValidationContext::new(consignment.schema.clone());
+        assert!(context.is_empty());
+
+        let status = context.validate_incremental(&consignment, genesis);
+        assert_eq!(status.failures, vec![]);
+        assert_eq!((context.hits(), context.misses()), (0, 1));
+        assert!(context.is_validated(genesis.id()));
+
+        assert_eq!(context.validate_incremental(&consignment, genesis), status);
+        assert_eq!((context.hits(), context.misses()), (1, 1));
+
+        // the same schema keeps the cache
+        context.set_schema(consignment.schema.clone());
+        assert_eq!(context.len(), 1);
+
+        let mut other = consignment.schema.clone();
+        other
+            .transitions
+            .insert(TransitionType::with(1), none!())
+            .unwrap();
+        context.set_schema(other);
+        assert!(context.is_empty());
+        context.validate_incremental(&consignment, genesis);
+        assert_eq!((context.hits(), context.misses()), (1, 2));
+
+        assert!(context.invalidate(genesis.id()));
+        assert!(!context.invalidate(genesis.id()));
+        context.validate_incremental(&consignment, genesis);
+        context.clear();
+        assert!(context.is_empty());
+        assert_eq!((context.hits(), context.misses()), (1, 3));
+    }
+
+    #[test]
+    fn failures_not_cached() {
+        let consignment = consignment(vec![]);
+        let genesis = OpRef::Genesis(&consignment.genesis);
+        let mut context = ValidationContext::new(consignment.schema.clone());
+        for misses in 1..=2 {
+            let status = context.validate_incremental(&consignment, genesis);
+            assert_eq!(status.failures.len(), 1);
+            assert!(!context.is_validated(genesis.id()));
+            assert_eq!((context.hits(), context.misses()), (0, misses));
+        }
+    }
+}
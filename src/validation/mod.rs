@@ -27,9 +27,16 @@ mod state;
 mod validator;
 mod consignment;
 mod status;
+mod context;
 
 pub use consignment::{AnchoredBundle, ConsignmentApi};
+pub use context::ValidationContext;
 pub(crate) use model::OpInfo;
 pub use script::VirtualMachine;
-pub use status::{Failure, Info, Status, Validity, Warning};
+pub use state::{
+    check_attachment_uniqueness, check_blinding_uniqueness, validate_fungible_history,
+    validate_fungible_operation, validate_fungible_operation_with, OutputCountPolicy,
+    StructuredConstraint, StructuredLenLimit, StructuredValueCheck,
+};
+pub use status::{Failure, Info, Severity, Status, StatusDiff, StatusSummary, Validity, Warning};
 pub use validator::{ResolveTx, TxResolverError, Validator};
@@ -29,7 +29,7 @@ use strict_types::SemId;
 use crate::schema::{AssignmentsSchema, GlobalSchema, ValencySchema};
 use crate::validation::{ConsignmentApi, VirtualMachine};
 use crate::{
-    validation, Assignments, AssignmentsRef, ExposedSeal, GlobalState, GlobalStateSchema,
+    validation, Assign, Assignments, AssignmentsRef, ExposedSeal, GlobalState, GlobalStateSchema,
     GlobalValues, GraphSeal, Inputs, OpFullType, OpId, OpRef, Operation, Opout, Redeemed, Schema,
     SchemaRoot, TypedAssigns, Valencies, BLANK_TRANSITION_ID,
 };
@@ -363,21 +363,53 @@ impl<Root: SchemaRoot> Schema<Root> {
 
             match owned_state.get(state_id) {
                 None => {}
-                Some(TypedAssigns::Declarative(set)) => set.iter().for_each(|data| {
-                    status += assignment.validate(&self.type_system, &id, *state_id, data)
-                }),
-                Some(TypedAssigns::Fungible(set)) => set.iter().for_each(|data| {
-                    status += assignment.validate(&self.type_system, &id, *state_id, data)
-                }),
-                Some(TypedAssigns::Structured(set)) => set.iter().for_each(|data| {
-                    status += assignment.validate(&self.type_system, &id, *state_id, data)
-                }),
-                Some(TypedAssigns::Attachment(set)) => set.iter().for_each(|data| {
-                    status += assignment.validate(&self.type_system, &id, *state_id, data)
-                }),
+                Some(TypedAssigns::Declarative(set)) => {
+                    status += assignment.validate_set(&self.type_system, &id, *state_id, set)
+                }
+                Some(TypedAssigns::Fungible(set)) => {
+                    status += assignment.validate_set(&self.type_system, &id, *state_id, set)
+                }
+                Some(TypedAssigns::Structured(set)) => {
+                    status += assignment.validate_set(&self.type_system, &id, *state_id, set)
+                }
+                Some(TypedAssigns::Attachment(set)) => {
+                    status += assignment.validate_set(&self.type_system, &id, *state_id, set)
+                }
             };
         }
 
+        // If every assignment is confidential structured or attachment state,
+        // which can't be validated, the operation state as a whole was not
+        // verified. Declarative and fungible state is always verified.
+        let assignments = owned_state
+            .values()
+            .map(|assigns| assigns.len_u16() as usize)
+            .sum::<usize>();
+        let verifiable = owned_state
+            .values()
+            .map(|assigns| match assigns {
+                TypedAssigns::Declarative(set) => set.len(),
+                TypedAssigns::Fungible(set) => set.len(),
+                TypedAssigns::Structured(set) => {
+                    set.iter().filter_map(Assign::as_revealed_state).count()
+                }
+                TypedAssigns::Attachment(set) => {
+                    set.iter().filter_map(Assign::as_revealed_state).count()
+                }
+            })
+            .sum::<usize>();
+        if assignments > 0 && verifiable == 0 {
+            status.add_info(validation::Info::OperationFullyConfidential { opid: id });
+        }
+
+        let attaches = owned_state
+            .values()
+            .flat_map(TypedAssigns::as_attachment)
+            .filter_map(Assign::as_revealed_state)
+            .cloned()
+            .collect::<Vec<_>>();
+        status += validation::check_attachment_uniqueness(id, &attaches);
+
         status
     }
 
@@ -552,3 +584,88 @@ fn extract_redeemed_valencies<C: ConsignmentApi>(
     }
     public_rights
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::SmallVec;
+    use commit_verify::Conceal;
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::schema::{AssignmentType, MediaType, StateSchema};
+    use crate::{
+        AssignAttach, AssignRights, AttachId, Occurrences, RevealedAttach, SubSchema, VoidState,
+    };
+
+    const TY: AssignmentType = AssignmentType::with(1);
+    const TY2: AssignmentType = AssignmentType::with(2);
+
+    fn attach_assignments(confidential: bool) -> Assignments<GraphSeal> {
+        let seal = GraphSeal::strict_dumb();
+        let attach = RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any);
+        let assign = if confidential {
+            AssignAttach::ConfidentialState {
+                seal,
+                state: attach.conceal(),
+            }
+        } else {
+            AssignAttach::revealed(seal, attach)
+        };
+        let set = SmallVec::try_from_iter([assign]).unwrap();
+        Assignments::from_inner(tiny_bmap! { TY => TypedAssigns::Attachment(set) })
+    }
+
+    #[test]
+    fn operation_fully_confidential() {
+        let schema = SubSchema {
+            owned_types: tiny_bmap! { TY => StateSchema::Attachment(MediaType::Any) },
+            ..default!()
+        };
+        let assign_schema = tiny_bmap! { TY => Occurrences::Once };
+        let opid = OpId::strict_dumb();
+        let fully_confidential = validation::Info::OperationFullyConfidential { opid };
+
+        let status = schema.validate_owned_state(opid, &attach_assignments(true), &assign_schema);
+        assert!(status.failures.is_empty());
+        assert!(status.info.contains(&fully_confidential));
+
+        let status = schema.validate_owned_state(opid, &attach_assignments(false), &assign_schema);
+        assert!(status.failures.is_empty());
+        assert!(!status.info.contains(&fully_confidential));
+
+        let status =
+            schema.validate_owned_state(opid, &Assignments::<GraphSeal>::default(), &empty!());
+        assert!(!status.info.contains(&fully_confidential));
+
+        // Confidential attachment alongside always verified declarative state
+        let schema = SubSchema {
+            owned_types: tiny_bmap! {
+                TY => StateSchema::Attachment(MediaType::Any),
+                TY2 => StateSchema::Declarative,
+            },
+            ..default!()
+        };
+        let assign_schema = tiny_bmap! { TY => Occurrences::Once, TY2 => Occurrences::NoneOrMore };
+        let mut mixed = attach_assignments(true);
+        let declarative = SmallVec::try_from_iter([AssignRights::revealed(
+            GraphSeal::strict_dumb(),
+            VoidState::default(),
+        )])
+        .unwrap();
+        mixed
+            .insert(TY2, TypedAssigns::Declarative(declarative))
+            .unwrap();
+        let status = schema.validate_owned_state(opid, &mixed, &assign_schema);
+        assert!(status.failures.is_empty());
+        assert!(!status.info.contains(&fully_confidential));
+
+        // An empty set of declarative assignments doesn't make the state
+        // verified
+        let mut empty_declarative = attach_assignments(true);
+        empty_declarative
+            .insert(TY2, TypedAssigns::Declarative(empty!()))
+            .unwrap();
+        let status = schema.validate_owned_state(opid, &empty_declarative, &assign_schema);
+        assert!(status.info.contains(&fully_confidential));
+    }
+}
@@ -21,14 +21,24 @@
 // limitations under the License.
 
 use crate::validation::Status;
+use crate::vm::EntryPoint;
 use crate::{
-    validation, OpFullType, OpSchema, Schema, StateSchema, SubSchema, BLANK_TRANSITION_ID,
+    validation, OpFullType, OpSchema, Schema, Script, StateSchema, SubSchema, BLANK_TRANSITION_ID,
 };
 
 impl SubSchema {
-    pub fn verify(&self) -> validation::Status {
+    pub fn verify(&self) -> validation::Status { self.verify_with(false) }
+
+    /// Verifies schema in the same way as [`SubSchema::verify`], additionally
+    /// requiring the schema script to define a genesis validator if
+    /// `requires_genesis_validator` is set.
+    pub fn verify_with(&self, requires_genesis_validator: bool) -> validation::Status {
         let mut status = validation::Status::new();
 
+        if requires_genesis_validator {
+            status += self.verify_genesis_validator();
+        }
+
         if let Some(ref root) = self.subset_of {
             status += self.verify_subschema(root);
         }
@@ -39,6 +49,18 @@ impl SubSchema {
         status
     }
 
+    fn verify_genesis_validator(&self) -> validation::Status {
+        let mut status = validation::Status::new();
+        let Script::AluVM(ref script) = self.script;
+        if !script
+            .entry_points
+            .contains_key(&EntryPoint::ValidateGenesis)
+        {
+            status.add_failure(validation::Failure::MissingGenesisValidator);
+        }
+        status
+    }
+
     fn verify_consistency(&self) -> validation::Status {
         let mut status = validation::Status::new();
 
@@ -263,3 +285,33 @@ where T: OpSchema
         status
     }
 }
+
+#[cfg(test)]
+mod test {
+    use aluvm::library::{Lib, LibSeg, LibSite};
+    use amplify::confinement::Confined;
+
+    use super::*;
+    use crate::vm::AluScript;
+
+    #[test]
+    fn genesis_validator_requirement() {
+        let mut schema = SubSchema::default();
+        assert_eq!(schema.verify_with(false), schema.verify());
+        assert!(schema
+            .verify_with(true)
+            .failures
+            .contains(&validation::Failure::MissingGenesisValidator));
+
+        let lib = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        schema.script = Script::AluVM(AluScript {
+            entry_points: Confined::try_from_iter([(
+                EntryPoint::ValidateGenesis,
+                LibSite::with(0, lib.id()),
+            )])
+            .unwrap(),
+            libs: Confined::try_from_iter([(lib.id(), lib)]).unwrap(),
+        });
+        assert_eq!(schema.verify_with(true), schema.verify());
+    }
+}
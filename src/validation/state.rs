@@ -20,14 +20,108 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use strict_types::TypeSystem;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead};
+
+use amplify::confinement::SmallVec;
+use strict_encoding::{DecodeError, StrictDecode, StrictReader};
+use strict_types::typify::TypedVal;
+use strict_types::{SemId, StrictVal, Ty, TypeSystem};
 
 use crate::schema::AssignmentType;
 use crate::{
-    validation, Assign, ConfidentialState, ExposedSeal, ExposedState, OpId, StateCommitment,
-    StateData, StateSchema,
+    is_potentially_dangerous, validation, Assign, ConcealedValue, ConfidentialState, ExposedSeal,
+    ExposedState, OpId, OpType, Operation, Opout, PedersenCommitment, RevealedAttach, RevealedData,
+    RevealedValue, Schema, SchemaRoot, StateCommitment, StateData, StateSchema,
 };
 
+/// Custom semantic constraint on structured owned state, checked after the
+/// state data were successfully deserialized into a value of the semantic type
+/// required by the schema.
+///
+/// Implemented for any `Fn(AssignmentType, SemId, &StrictVal) -> bool`; checks
+/// reporting the reason of the value rejection are provided by
+/// [`StructuredValueCheck`].
+pub trait StructuredConstraint {
+    /// Returns whether the deserialized value of the owned state satisfies
+    /// the constraint.
+    fn check(&self, state_type: AssignmentType, sem_id: SemId, value: &StrictVal) -> bool;
+
+    /// Checks the deserialized value of the owned state, returning the reason
+    /// of its rejection, if the constraint provides one.
+    ///
+    /// Values rejected without a reason are reported with
+    /// [`validation::Failure::SchemaOwnedValueConstraintViolated`], and with
+    /// a reason - with [`validation::Failure::StructuredValueRejected`].
+    fn verify(
+        &self,
+        state_type: AssignmentType,
+        sem_id: SemId,
+        value: &StrictVal,
+    ) -> Result<(), Option<String>> {
+        if self.check(state_type, sem_id, value) {
+            Ok(())
+        } else {
+            Err(None)
+        }
+    }
+}
+
+impl<F> StructuredConstraint for F
+where F: Fn(AssignmentType, SemId, &StrictVal) -> bool
+{
+    fn check(&self, state_type: AssignmentType, sem_id: SemId, value: &StrictVal) -> bool {
+        self(state_type, sem_id, value)
+    }
+}
+
+/// Constraint on structured owned state from a `Fn(&StrictVal) -> Result<(),
+/// String>` check of the deserialized value, returning the reason of the
+/// value rejection as an error.
+///
+/// The check allows schemas to enforce simple constraints on the value, like
+/// numeric fields being multiples of a base unit, without a validation script.
+pub struct StructuredValueCheck<F>(pub F)
+where F: Fn(&StrictVal) -> Result<(), String>;
+
+impl<F> StructuredConstraint for StructuredValueCheck<F>
+where F: Fn(&StrictVal) -> Result<(), String>
+{
+    fn check(&self, _: AssignmentType, _: SemId, value: &StrictVal) -> bool {
+        (self.0)(value).is_ok()
+    }
+
+    fn verify(&self, _: AssignmentType, _: SemId, value: &StrictVal) -> Result<(), Option<String>> {
+        (self.0)(value).map_err(Some)
+    }
+}
+
+/// Limit on the length of revealed structured owned state, checked by
+/// [`StateSchema::validate_with_limit`] before the state is deserialized.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum StructuredLenLimit {
+    /// Structured state of any length is accepted.
+    #[default]
+    None,
+    /// State longer than the given number of bytes is accepted, but reported
+    /// with [`validation::Warning::LargeStructuredState`].
+    Warn(usize),
+    /// State longer than the given number of bytes is rejected with
+    /// [`validation::Failure::StructuredStateTooLarge`] without being
+    /// deserialized.
+    Deny(usize),
+}
+
+impl StructuredLenLimit {
+    fn warns(self, len: usize) -> bool {
+        matches!(self, StructuredLenLimit::Warn(max) if len > max)
+    }
+
+    fn denies(self, len: usize) -> bool {
+        matches!(self, StructuredLenLimit::Deny(max) if len > max)
+    }
+}
+
 impl StateSchema {
     pub fn validate<State: ExposedState, Seal: ExposedSeal>(
         &self,
@@ -35,19 +129,66 @@ impl StateSchema {
         opid: &OpId,
         state_type: AssignmentType,
         data: &Assign<State, Seal>,
+    ) -> validation::Status {
+        self.validate_constrained(type_system, opid, state_type, data, None)
+    }
+
+    /// Validates assignment against the state schema, additionally checking
+    /// revealed structured state against the custom `constraint`, if any.
+    ///
+    /// Without a constraint the validation is equivalent to
+    /// [`StateSchema::validate`].
+    pub fn validate_constrained<State: ExposedState, Seal: ExposedSeal>(
+        &self,
+        type_system: &TypeSystem,
+        opid: &OpId,
+        state_type: AssignmentType,
+        data: &Assign<State, Seal>,
+        constraint: Option<&dyn StructuredConstraint>,
+    ) -> validation::Status {
+        self.validate_with_limit(
+            type_system,
+            opid,
+            state_type,
+            data,
+            constraint,
+            StructuredLenLimit::None,
+        )
+    }
+
+    /// Validates assignment against the state schema like
+    /// [`StateSchema::validate_constrained`], additionally checking the length
+    /// of revealed structured state against the `limit` before the state is
+    /// deserialized.
+    pub fn validate_with_limit<State: ExposedState, Seal: ExposedSeal>(
+        &self,
+        type_system: &TypeSystem,
+        opid: &OpId,
+        state_type: AssignmentType,
+        data: &Assign<State, Seal>,
+        constraint: Option<&dyn StructuredConstraint>,
+        limit: StructuredLenLimit,
     ) -> validation::Status {
         let mut status = validation::Status::new();
         match data {
             Assign::Confidential { state, .. } | Assign::ConfidentialState { state, .. } => {
                 match (self, state.state_commitment()) {
                     (StateSchema::Declarative, StateCommitment::Void) => {}
+                    (StateSchema::FungiblePublic(_), StateCommitment::Fungible(_)) => {
+                        status.add_failure(validation::Failure::ConfidentialityNotAllowed {
+                            opid: *opid,
+                            state_type,
+                        });
+                    }
                     (StateSchema::Fungible(_), StateCommitment::Fungible(value)) => {
+                        // Range proof width is not checked against the fungible type: both
+                        // `FungibleType` and range proofs are 64-bit only, so they can't
+                        // mismatch. Once narrower types are added, the check must compare
+                        // `FungibleType::bits` against `RangeProof::bits` here.
                         // [SECURITY-CRITICAL]: Bulletproofs validation
                         if let Err(err) = value.verify_range_proof() {
                             status.add_failure(validation::Failure::BulletproofsInvalid(
-                                *opid,
-                                state_type,
-                                err.to_string(),
+                                *opid, state_type, err,
                             ));
                         }
                     }
@@ -56,7 +197,10 @@ impl StateSchema {
                             *opid, state_type,
                         ));
                     }
-                    (StateSchema::Attachment(_), StateCommitment::Attachment(_)) => {
+                    (
+                        StateSchema::Attachment(_) | StateSchema::AttachmentAnyOf(_),
+                        StateCommitment::Attachment(_),
+                    ) => {
                         status.add_info(validation::Info::UncheckableConfidentialState(
                             *opid, state_type,
                         ));
@@ -85,26 +229,118 @@ impl StateSchema {
                             found: attach.media_type,
                         });
                     }
-                    (StateSchema::Fungible(schema), StateData::Fungible(v))
-                        if v.value.fungible_type() != *schema =>
+                    (StateSchema::AttachmentAnyOf(allowed), StateData::Attachment(attach))
+                        if !self.accepts_media_type(&attach.media_type) =>
                     {
+                        status.add_failure(validation::Failure::MediaTypeNotAllowed {
+                            opid: *opid,
+                            state_type,
+                            allowed: allowed.iter().cloned().collect(),
+                            found: attach.media_type,
+                        });
+                    }
+                    (
+                        StateSchema::Attachment(_) | StateSchema::AttachmentAnyOf(_),
+                        StateData::Attachment(attach),
+                    ) => {
+                        if is_potentially_dangerous(&attach.media_type) {
+                            status.add_warning(
+                                validation::Warning::PotentiallyDangerousAttachment {
+                                    opid: *opid,
+                                    state_type,
+                                    media_type: attach.media_type,
+                                },
+                            );
+                        }
+                    }
+                    (
+                        StateSchema::Fungible(ty) | StateSchema::FungiblePublic(ty),
+                        StateData::Fungible(v),
+                    ) if v.value.fungible_type() != *ty => {
                         status.add_failure(validation::Failure::FungibleTypeMismatch {
                             opid: *opid,
                             state_type,
-                            expected: *schema,
+                            expected: *ty,
                             found: v.value.fungible_type(),
                         });
                     }
-                    (StateSchema::Fungible(_), StateData::Fungible(_)) => {}
+                    (
+                        StateSchema::Fungible(_) | StateSchema::FungiblePublic(_),
+                        StateData::Fungible(_),
+                    ) => {}
+                    (StateSchema::Structured(_), StateData::Structured(data))
+                        if limit.denies(data.as_ref().len()) =>
+                    {
+                        status.add_failure(validation::Failure::StructuredStateTooLarge {
+                            opid: *opid,
+                            state_type,
+                            len: data.as_ref().len(),
+                        });
+                    }
                     (StateSchema::Structured(sem_id), StateData::Structured(data)) => {
-                        if type_system
-                            .strict_deserialize_type(*sem_id, data.as_ref())
-                            .is_err()
+                        let found = data.as_ref().len();
+                        if limit.warns(found) {
+                            status.add_warning(validation::Warning::LargeStructuredState {
+                                opid: *opid,
+                                state_type,
+                                len: found,
+                            });
+                        }
+                        if let Some(expected) =
+                            fixed_encoded_len(type_system, *sem_id).filter(|len| *len != found)
                         {
-                            status.add_failure(validation::Failure::SchemaInvalidOwnedValue(
-                                *opid, state_type, *sem_id,
-                            ));
-                        };
+                            status.add_failure(
+                                validation::Failure::StructuredStateLengthMismatch {
+                                    opid: *opid,
+                                    state_type,
+                                    sem_id: *sem_id,
+                                    expected,
+                                    found,
+                                },
+                            );
+                        } else {
+                            match type_system.strict_deserialize_type(*sem_id, data.as_ref()) {
+                                Err(_) => {
+                                    status.add_failure(
+                                        validation::Failure::SchemaInvalidOwnedValue(
+                                            *opid, state_type, *sem_id,
+                                        ),
+                                    );
+                                }
+                                Ok(value) if !is_canonical(type_system, &value, data.as_ref()) => {
+                                    status.add_failure(
+                                        validation::Failure::NonCanonicalStructuredState {
+                                            opid: *opid,
+                                            state_type,
+                                            sem_id: *sem_id,
+                                        },
+                                    );
+                                }
+                                Ok(value) => match constraint
+                                    .map(|c| c.verify(state_type, *sem_id, value.as_val()))
+                                {
+                                    None | Some(Ok(())) => {}
+                                    Some(Err(None)) => {
+                                        status.add_failure(
+                                            validation::Failure::SchemaOwnedValueConstraintViolated {
+                                                opid: *opid,
+                                                state_type,
+                                                sem_id: *sem_id,
+                                            },
+                                        );
+                                    }
+                                    Some(Err(Some(reason))) => {
+                                        status.add_failure(
+                                            validation::Failure::StructuredValueRejected {
+                                                opid: *opid,
+                                                state_type,
+                                                reason,
+                                            },
+                                        );
+                                    }
+                                },
+                            }
+                        }
                     }
                     // all other options are mismatches
                     (state_schema, found) => {
@@ -120,4 +356,1359 @@ impl StateSchema {
         }
         status
     }
+
+    /// Validates a whole set of assignments of a single assignment type
+    /// against the state schema, accumulating per-item validation statuses.
+    ///
+    /// Each of the items must resolve to the [`StateSchema::state_type`];
+    /// otherwise a [`validation::Failure::StateTypeMismatch`] is reported for
+    /// the item.
+    pub fn validate_set<State: ExposedState, Seal: ExposedSeal>(
+        &self,
+        type_system: &TypeSystem,
+        opid: &OpId,
+        state_type: AssignmentType,
+        items: &[Assign<State, Seal>],
+    ) -> validation::Status {
+        let mut status = validation::Status::new();
+        for data in items {
+            status += self.validate(type_system, opid, state_type, data);
+        }
+        status
+    }
+
+    /// Decodes revealed state data of the type defined by the state schema
+    /// from raw `bytes`:
+    /// - declarative state must have no data;
+    /// - fungible state is a strict-encoded [`RevealedValue`] of the schema
+    ///   fungible type;
+    /// - structured state is the data of the schema semantic type, as defined
+    ///   by the `type_system`;
+    /// - attachment is a strict-encoded [`RevealedAttach`].
+    ///
+    /// The data must be entirely consumed by the decoding. Only the data
+    /// format is checked; the rest of the validation rules are applied by
+    /// [`StateSchema::validate`].
+    pub fn decode_state_data(
+        &self,
+        type_system: &TypeSystem,
+        bytes: &[u8],
+    ) -> Result<StateData, DecodeError> {
+        match self {
+            StateSchema::Declarative if bytes.is_empty() => Ok(StateData::Void),
+            StateSchema::Declarative => {
+                Err(DecodeError::DataIntegrityError(s!("declarative state must not have data")))
+            }
+            StateSchema::Fungible(ty) | StateSchema::FungiblePublic(ty) => {
+                let value = decode_exact::<RevealedValue>(bytes)?;
+                if value.value.fungible_type() != *ty {
+                    return Err(DecodeError::DataIntegrityError(format!(
+                        "fungible state of type {} while {ty} is expected",
+                        value.value.fungible_type()
+                    )));
+                }
+                Ok(StateData::Fungible(value))
+            }
+            StateSchema::Structured(sem_id) => {
+                type_system
+                    .strict_deserialize_type(*sem_id, bytes)
+                    .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))?;
+                let data = SmallVec::try_from(bytes.to_vec())?;
+                Ok(StateData::Structured(RevealedData::from(data)))
+            }
+            StateSchema::Attachment(_) | StateSchema::AttachmentAnyOf(_) => {
+                decode_exact(bytes).map(StateData::Attachment)
+            }
+        }
+    }
+}
+
+/// Strict-decodes value from `bytes`, requiring all the data to be consumed.
+fn decode_exact<T: StrictDecode>(bytes: &[u8]) -> Result<T, DecodeError> {
+    let mut reader = StrictReader::with(bytes.len(), io::Cursor::new(bytes));
+    let value = T::strict_decode(&mut reader)?;
+    if !reader.unbox().fill_buf()?.is_empty() {
+        return Err(DecodeError::DataIntegrityError(s!("data are not entirely consumed")));
+    }
+    Ok(value)
+}
+
+impl<Root: SchemaRoot> Schema<Root> {
+    /// Validates assignment of the `state_type` against the state schema
+    /// declared for this type by the schema, using the schema type system.
+    ///
+    /// Unlike [`StateSchema::validate`], which trusts the caller to provide
+    /// the state schema matching the assignment type, reports
+    /// [`validation::Failure::AssignmentTypeNotInSchema`] if the schema
+    /// doesn't declare the assignment type.
+    pub fn validate_assignment<State: ExposedState, Seal: ExposedSeal>(
+        &self,
+        opid: &OpId,
+        state_type: AssignmentType,
+        data: &Assign<State, Seal>,
+    ) -> validation::Status {
+        match self.owned_types.get(&state_type) {
+            Some(state_schema) => state_schema.validate(&self.type_system, opid, state_type, data),
+            None => {
+                validation::Status::with_failure(validation::Failure::AssignmentTypeNotInSchema {
+                    opid: *opid,
+                    state_type,
+                })
+            }
+        }
+    }
+}
+
+/// Returns length of the strict-encoded data for the semantic type, if the
+/// type always has the same encoded length. Returns `None` for variable-length
+/// types and types unknown to the type system.
+fn fixed_encoded_len(type_system: &TypeSystem, sem_id: SemId) -> Option<usize> {
+    match type_system.get(sem_id)? {
+        Ty::Primitive(prim) => Some(prim.byte_size() as usize),
+        Ty::Enum(_) => Some(1),
+        Ty::Tuple(fields) => fields
+            .iter()
+            .map(|ty| fixed_encoded_len(type_system, *ty))
+            .sum(),
+        Ty::Struct(fields) => fields
+            .iter()
+            .map(|field| fixed_encoded_len(type_system, field.ty))
+            .sum(),
+        Ty::Array(ty, len) => fixed_encoded_len(type_system, *ty).map(|size| size * *len as usize),
+        Ty::UnicodeChar | Ty::Union(_) | Ty::List(..) | Ty::Set(..) | Ty::Map(..) => None,
+    }
+}
+
+/// Checks that `data`, deserialized into `value`, are the canonical encoding
+/// of the value: re-encoding the value through the type system must give back
+/// byte-identical data.
+///
+/// The deserializer alone accepts repeated set values and map keys and
+/// collections outside of the type size bounds, so the value is re-checked
+/// against the type system before being encoded.
+fn is_canonical(type_system: &TypeSystem, value: &TypedVal, data: &[u8]) -> bool {
+    let sem_id = value.as_orig().id;
+    let Ok(value) = type_system.typify(value.as_val().clone(), sem_id) else {
+        return false;
+    };
+    let mut canonical = Vec::with_capacity(data.len());
+    type_system
+        .strict_write_type(&value, &mut canonical)
+        .is_ok() &&
+        canonical == data
+}
+
+/// Checks that all revealed attachments of an operation have distinct
+/// attachment ids, reporting [`validation::Warning::DuplicateAttachment`] for
+/// each of the ids used more than once.
+pub fn check_attachment_uniqueness(opid: OpId, attaches: &[RevealedAttach]) -> validation::Status {
+    let mut status = validation::Status::new();
+    let mut known = BTreeSet::new();
+    let mut reported = BTreeSet::new();
+    for attach in attaches {
+        if !known.insert(attach.id) && reported.insert(attach.id) {
+            status.add_warning(validation::Warning::DuplicateAttachment {
+                opid,
+                id: attach.id,
+            });
+        }
+    }
+    status
+}
+
+/// Checks that all revealed fungible `values` of the `state_type` (usually the
+/// outputs of operation `opid`) have distinct blinding factors, reporting
+/// [`validation::Warning::ReusedBlindingFactor`] with the positions of the
+/// values for each of the blinding factors used more than once.
+///
+/// Blinding factor reuse is not a consensus violation and may be intentional,
+/// but usually indicates a bug in the wallet constructing the operation.
+pub fn check_blinding_uniqueness(
+    opid: OpId,
+    state_type: AssignmentType,
+    values: &[RevealedValue],
+) -> validation::Status {
+    let mut status = validation::Status::new();
+    let mut known = BTreeMap::<_, Vec<usize>>::new();
+    for (index, value) in values.iter().enumerate() {
+        known.entry(value.blinding).or_default().push(index);
+    }
+    let mut reused = known
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .collect::<Vec<_>>();
+    reused.sort();
+    for indices in reused {
+        status.add_warning(validation::Warning::ReusedBlindingFactor {
+            opid,
+            state_type,
+            indices,
+        });
+    }
+    status
+}
+
+/// Validates balance of the fungible state of a single assignment type in an
+/// operation, checking that the sum of `inputs` with optional `issuance` is
+/// equal to the sum of `outputs` with optional `burn`, and that all inputs and
+/// outputs have valid range proofs.
+///
+/// Reports [`validation::Failure::BalanceMismatch`] if the sums are not equal
+/// and [`validation::Failure::BulletproofsInvalid`] for each of the values
+/// with an invalid range proof.
+pub fn validate_fungible_operation(
+    opid: OpId,
+    state_type: AssignmentType,
+    inputs: &[ConcealedValue],
+    outputs: &[ConcealedValue],
+    issuance: Option<PedersenCommitment>,
+    burn: Option<PedersenCommitment>,
+) -> validation::Status {
+    validate_fungible_operation_with(
+        opid,
+        state_type,
+        inputs,
+        outputs,
+        issuance,
+        burn,
+        OutputCountPolicy::default(),
+    )
+}
+
+/// Policy on the number of fungible outputs of an operation, checked by
+/// [`validate_fungible_operation_with`]. By default, the number of outputs is
+/// not constrained.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct OutputCountPolicy {
+    /// Minimal number of outputs; setting it to one forbids operations
+    /// without fungible outputs, which burn the whole input value.
+    pub min_outputs: usize,
+    /// Maximal number of outputs, if limited.
+    pub max_outputs: Option<usize>,
+}
+
+/// Validates balance of the fungible state like
+/// [`validate_fungible_operation`], additionally checking the number of
+/// `outputs` against the `policy`.
+///
+/// Reports [`validation::Failure::OutputCountOutOfRange`] with the violated
+/// bound if the number of outputs is out of the allowed range.
+pub fn validate_fungible_operation_with(
+    opid: OpId,
+    state_type: AssignmentType,
+    inputs: &[ConcealedValue],
+    outputs: &[ConcealedValue],
+    issuance: Option<PedersenCommitment>,
+    burn: Option<PedersenCommitment>,
+    policy: OutputCountPolicy,
+) -> validation::Status {
+    let mut status = validation::Status::new();
+
+    let count = outputs.len();
+    let violated = if count < policy.min_outputs {
+        Some(policy.min_outputs)
+    } else {
+        policy.max_outputs.filter(|max| count > *max)
+    };
+    if let Some(bound) = violated {
+        status.add_failure(validation::Failure::OutputCountOutOfRange {
+            opid,
+            state_type,
+            count,
+            bound,
+        });
+    }
+
+    let mut input_commitments = inputs.iter().map(|v| v.commitment).collect::<Vec<_>>();
+    input_commitments.extend(issuance);
+    let mut output_commitments = outputs.iter().map(|v| v.commitment).collect::<Vec<_>>();
+    output_commitments.extend(burn);
+    if !PedersenCommitment::verify_sum(&input_commitments, &output_commitments) {
+        status.add_failure(validation::Failure::BalanceMismatch { opid, state_type });
+    }
+
+    // [SECURITY-CRITICAL]: Bulletproofs validation
+    for value in inputs.iter().chain(outputs) {
+        if let Err(err) = value.verify_range_proof() {
+            status.add_failure(validation::Failure::BulletproofsInvalid(opid, state_type, err));
+        }
+    }
+
+    status
+}
+
+/// Validates fungible state of all `ops` forming a contract history: checks
+/// range proofs of all confidential fungible outputs and, for state
+/// transitions, balance of the inputs against the outputs of each fungible
+/// state type.
+///
+/// Inputs are resolved against the outputs of the operations from the same
+/// slice; an input spent by the transition which can't be resolved is
+/// reported as [`validation::Failure::NoPrevOut`] and the balance of its state
+/// type is not checked. Inputs spending non-fungible state don't affect the
+/// balance. Genesis and state extensions issue new state, so their balance is
+/// not validated.
+///
+/// Each operation is validated independently of the others, so a failure in
+/// one operation doesn't prevent validation of the rest of the history. With
+/// the `rayon` feature the operations are validated in parallel. In both
+/// cases the resulting status lists the failures in the order of `ops`, and
+/// thus is deterministic for the same history.
+pub fn validate_fungible_history<Op: Operation + Sync>(ops: &[Op]) -> validation::Status {
+    // All outputs of the history, with commitments for the fungible ones
+    let mut outputs = BTreeMap::<Opout, Option<PedersenCommitment>>::new();
+    for op in ops {
+        let opid = op.id();
+        let assignments = op.assignments();
+        for ty in assignments.types() {
+            let Some(assigns) = assignments.get(ty) else {
+                continue;
+            };
+            for no in 0..assigns.len_u16() {
+                outputs.insert(Opout::new(opid, ty, no), None);
+            }
+            for (no, assign) in assigns.as_fungible().iter().enumerate() {
+                if let StateCommitment::Fungible(value) = assign.state_commitment() {
+                    outputs.insert(Opout::new(opid, ty, no as u16), Some(value.commitment));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    let statuses = {
+        use rayon::prelude::*;
+        ops.par_iter()
+            .map(|op| validate_fungible_history_op(op, &outputs))
+            .collect::<Vec<_>>()
+    };
+    #[cfg(not(feature = "rayon"))]
+    let statuses = ops
+        .iter()
+        .map(|op| validate_fungible_history_op(op, &outputs));
+
+    // Statuses are merged in the order of `ops`
+    statuses
+        .into_iter()
+        .fold(validation::Status::new(), |mut status, op_status| {
+            status += op_status;
+            status
+        })
+}
+
+fn validate_fungible_history_op(
+    op: &impl Operation,
+    outputs: &BTreeMap<Opout, Option<PedersenCommitment>>,
+) -> validation::Status {
+    let mut status = validation::Status::new();
+    let opid = op.id();
+
+    let mut balances = BTreeMap::<AssignmentType, (Vec<_>, Vec<_>)>::new();
+    let assignments = op.assignments();
+    for ty in assignments.types() {
+        let Some(assigns) = assignments.get(ty) else {
+            continue;
+        };
+        for assign in assigns.as_fungible() {
+            // [SECURITY-CRITICAL]: Bulletproofs validation
+            if assign.as_revealed_state().is_none() {
+                if let Err(err) = assign.to_confidential_state().verify_range_proof() {
+                    status.add_failure(validation::Failure::BulletproofsInvalid(opid, ty, err));
+                }
+            }
+            if let StateCommitment::Fungible(value) = assign.state_commitment() {
+                balances.entry(ty).or_default().1.push(value.commitment);
+            }
+        }
+    }
+
+    if op.op_type() != OpType::StateTransition {
+        return status;
+    }
+
+    let mut unresolved = BTreeSet::new();
+    for input in &op.inputs() {
+        let prev_out = input.prev_out;
+        match outputs.get(&prev_out) {
+            Some(Some(commitment)) => balances.entry(prev_out.ty).or_default().0.push(*commitment),
+            Some(None) => {}
+            None => {
+                unresolved.insert(prev_out.ty);
+                status.add_failure(validation::Failure::NoPrevOut(opid, prev_out));
+            }
+        }
+    }
+
+    for (state_type, (inputs, outputs)) in balances {
+        if unresolved.contains(&state_type) {
+            continue;
+        }
+        if !PedersenCommitment::verify_sum(&inputs, &outputs) {
+            status.add_failure(validation::Failure::BalanceMismatch { opid, state_type });
+        }
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::{SmallVec, TinyOrdSet};
+    use amplify::Array;
+    use bp::secp256k1::rand::thread_rng;
+    use commit_verify::{CommitVerify, Conceal};
+    use strict_encoding::StrictDumb;
+    use strict_types::typelib::{Dependency, LibBuilder};
+    use strict_types::typesys::SystemBuilder;
+
+    use super::*;
+    use crate::{
+        AttachId, BlindingFactor, FungibleType, GraphSeal, MediaType, RangeProof, RangeProofError,
+        RevealedAttach, RevealedData, RevealedValue, StateType, BULLETPROOF_LEN,
+    };
+
+    #[derive(Clone, Copy, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test")]
+    struct Small(u8);
+
+    #[derive(Clone, Copy, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test")]
+    struct Pair(u8, u16);
+
+    #[derive(Clone, Copy, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test")]
+    struct Optional(u8, Option<u8>);
+
+    #[derive(Clone, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test")]
+    struct Bag(TinyOrdSet<u8>);
+
+    const TY: AssignmentType = AssignmentType::with(0);
+
+    fn blinding(byte: u8) -> BlindingFactor { BlindingFactor::try_from([byte; 32]).unwrap() }
+
+    #[test]
+    fn validate_set_matching() {
+        let schema = StateSchema::fungible(FungibleType::Unsigned64Bit);
+        let seal = GraphSeal::strict_dumb();
+        let items = [
+            Assign::revealed(seal, RevealedValue::with(5u64, blinding(1))),
+            Assign::revealed(seal, RevealedValue::with(7u64, blinding(2))),
+        ];
+        let status = schema.validate_set(&TypeSystem::new(), &OpId::strict_dumb(), TY, &items[..]);
+        assert!(status.failures.is_empty());
+    }
+
+    #[test]
+    fn assignment_type_not_in_schema() {
+        let schema = crate::SubSchema {
+            owned_types: tiny_bmap! { TY => StateSchema::fungible(FungibleType::Unsigned64Bit) },
+            ..default!()
+        };
+        let opid = OpId::strict_dumb();
+        let assign =
+            Assign::revealed(GraphSeal::strict_dumb(), RevealedValue::with(5u64, blinding(1)));
+        assert!(schema
+            .validate_assignment(&opid, TY, &assign)
+            .failures
+            .is_empty());
+
+        let undeclared = AssignmentType::with(7);
+        assert_eq!(
+            schema
+                .validate_assignment(&opid, undeclared, &assign)
+                .failures,
+            vec![validation::Failure::AssignmentTypeNotInSchema {
+                opid,
+                state_type: undeclared
+            }]
+        );
+        // the state schema alone can't detect the wrong assignment type
+        let state_schema = &schema.owned_types[&TY];
+        assert!(state_schema
+            .validate(&schema.type_system, &opid, undeclared, &assign)
+            .failures
+            .is_empty());
+    }
+
+    #[test]
+    fn validate_set_mismatch() {
+        let schema = StateSchema::fungible(FungibleType::Unsigned64Bit);
+        let seal = GraphSeal::strict_dumb();
+        let attach = RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any);
+        let items = [
+            Assign::revealed(seal, StateData::Fungible(RevealedValue::with(5u64, blinding(1)))),
+            Assign::revealed(seal, StateData::Attachment(attach)),
+        ];
+        let status = schema.validate_set(&TypeSystem::new(), &OpId::strict_dumb(), TY, &items[..]);
+        assert_eq!(status.failures, vec![validation::Failure::StateTypeMismatch {
+            opid: OpId::strict_dumb(),
+            state_type: TY,
+            expected: StateType::Fungible,
+            found: StateType::Attachment,
+        }]);
+    }
+
+    #[test]
+    fn attachment_conforming() {
+        let opid = OpId::strict_dumb();
+        let attach = RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any);
+        let assign = Assign::revealed(GraphSeal::strict_dumb(), attach);
+        let status = StateSchema::Attachment(MediaType::Any).validate(
+            &TypeSystem::new(),
+            &opid,
+            TY,
+            &assign,
+        );
+        assert!(status.failures.is_empty());
+    }
+
+    #[test]
+    fn attachment_dangerous() {
+        let opid = OpId::strict_dumb();
+        let validate = |media_type| {
+            let attach = RevealedAttach::new(AttachId::strict_dumb(), media_type);
+            let assign = Assign::revealed(GraphSeal::strict_dumb(), attach);
+            StateSchema::Attachment(MediaType::Any).validate(&TypeSystem::new(), &opid, TY, &assign)
+        };
+
+        let status = validate(MediaType::ApplicationXSh);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.warnings, vec![validation::Warning::PotentiallyDangerousAttachment {
+            opid,
+            state_type: TY,
+            media_type: MediaType::ApplicationXSh,
+        }]);
+
+        let status = validate(MediaType::ImagePng);
+        assert!(status.failures.is_empty());
+        assert!(status.warnings.is_empty());
+    }
+
+    #[test]
+    fn attachment_any_of() {
+        let opid = OpId::strict_dumb();
+        let seal = GraphSeal::strict_dumb();
+        let attach = RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any);
+        let revealed = Assign::revealed(seal, attach.clone());
+        let concealed = Assign::<RevealedAttach, _>::Confidential {
+            seal: seal.conceal(),
+            state: attach.conceal(),
+        };
+        let validate = |schema: &StateSchema, assign: &Assign<RevealedAttach, GraphSeal>| {
+            schema
+                .validate(&TypeSystem::new(), &opid, TY, assign)
+                .failures
+        };
+
+        let single = StateSchema::Attachment(MediaType::Any);
+        let any_of = StateSchema::AttachmentAnyOf(tiny_bset![MediaType::Any]);
+        let none = StateSchema::AttachmentAnyOf(none!());
+        assert_eq!(any_of.state_type(), StateType::Attachment);
+        assert!(single.accepts_media_type(&MediaType::Any));
+        assert!(any_of.accepts_media_type(&MediaType::Any));
+        assert!(!none.accepts_media_type(&MediaType::Any));
+        assert!(!StateSchema::Declarative.accepts_media_type(&MediaType::Any));
+
+        assert!(validate(&any_of, &revealed).is_empty());
+        assert!(validate(&any_of, &concealed).is_empty());
+        assert!(validate(&none, &concealed).is_empty());
+        assert_eq!(validate(&none, &revealed), vec![validation::Failure::MediaTypeNotAllowed {
+            opid,
+            state_type: TY,
+            allowed: vec![],
+            found: MediaType::Any,
+        }]);
+    }
+
+    fn concealed(value: u64) -> Assign<RevealedValue, GraphSeal> {
+        let revealed = RevealedValue::with(value, blinding(1));
+        Assign::ConfidentialState {
+            seal: GraphSeal::strict_dumb(),
+            state: ConcealedValue {
+                commitment: PedersenCommitment::commit(&revealed),
+                range_proof: RangeProof::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn half_confidential_variants() {
+        let sys = TypeSystem::new();
+        let opid = OpId::strict_dumb();
+        let seal = GraphSeal::strict_dumb();
+        let value = RevealedValue::with(5u64, blinding(1));
+        let revealed = Assign::revealed(seal, value);
+        let confidential_seal = Assign::<RevealedValue, GraphSeal>::ConfidentialSeal {
+            seal: seal.conceal(),
+            state: value,
+        };
+        let confidential_state = concealed(5);
+        let confidential = Assign::<RevealedValue, GraphSeal>::Confidential {
+            seal: seal.conceal(),
+            state: confidential_state.to_confidential_state(),
+        };
+
+        let schemata = [
+            StateSchema::fungible(FungibleType::Unsigned64Bit),
+            StateSchema::fungible_public(FungibleType::Unsigned64Bit),
+            StateSchema::Declarative,
+            StateSchema::Structured(SemId::strict_dumb()),
+            StateSchema::Attachment(MediaType::Any),
+        ];
+        for schema in schemata {
+            // Revealed state is checked in the same way regardless of the seal
+            let status = schema.validate(&sys, &opid, TY, &revealed);
+            assert_eq!(schema.validate(&sys, &opid, TY, &confidential_seal), status);
+            // Concealed state is checked in the same way regardless of the seal
+            let status = schema.validate(&sys, &opid, TY, &confidential);
+            assert_eq!(schema.validate(&sys, &opid, TY, &confidential_state), status);
+        }
+
+        let schema = StateSchema::fungible_public(FungibleType::Unsigned64Bit);
+        let status = schema.validate(&sys, &opid, TY, &confidential_seal);
+        assert!(status.failures.is_empty());
+        let status = schema.validate(&sys, &opid, TY, &confidential_state);
+        assert_eq!(status.failures, vec![validation::Failure::ConfidentialityNotAllowed {
+            opid,
+            state_type: TY
+        }]);
+
+        let schema = StateSchema::Structured(SemId::strict_dumb());
+        let mismatch = validation::Failure::StateTypeMismatch {
+            opid,
+            state_type: TY,
+            expected: StateType::Structured,
+            found: StateType::Fungible,
+        };
+        let status = schema.validate(&sys, &opid, TY, &confidential_seal);
+        assert_eq!(status.failures, vec![mismatch.clone()]);
+        let status = schema.validate(&sys, &opid, TY, &confidential_state);
+        assert_eq!(status.failures, vec![mismatch]);
+    }
+
+    #[test]
+    fn range_proof_width() {
+        assert_eq!(RangeProof::default().bits(), None);
+        let proof = RangeProof::Bulletproof(Array::from([0xA5u8; BULLETPROOF_LEN]));
+        assert_eq!(proof.bits(), Some(FungibleType::Unsigned64Bit.bits()));
+        assert_eq!(RangeProof::Public(5).bits(), Some(FungibleType::Unsigned64Bit.bits()));
+    }
+
+    #[test]
+    fn fungible_confidential_allowed() {
+        let schema = StateSchema::fungible(FungibleType::Unsigned64Bit);
+        let status = schema.validate(&TypeSystem::new(), &OpId::strict_dumb(), TY, &concealed(5));
+        assert_eq!(status.failures, vec![validation::Failure::BulletproofsInvalid(
+            OpId::strict_dumb(),
+            TY,
+            RangeProofError::BulletproofsAbsent
+        )]);
+    }
+
+    #[test]
+    fn fungible_confidential_forbidden() {
+        let schema = StateSchema::fungible_public(FungibleType::Unsigned64Bit);
+        let status = schema.validate(&TypeSystem::new(), &OpId::strict_dumb(), TY, &concealed(5));
+        assert_eq!(status.failures, vec![validation::Failure::ConfidentialityNotAllowed {
+            opid: OpId::strict_dumb(),
+            state_type: TY
+        }]);
+
+        let revealed =
+            Assign::revealed(GraphSeal::strict_dumb(), RevealedValue::with(5u64, blinding(1)));
+        let status = schema.validate(&TypeSystem::new(), &OpId::strict_dumb(), TY, &revealed);
+        assert!(status.failures.is_empty());
+    }
+
+    #[test]
+    fn attachment_uniqueness() {
+        let opid = OpId::strict_dumb();
+        let id1 = AttachId::from([1u8; 32]);
+        let id2 = AttachId::from([2u8; 32]);
+        let attach1 = RevealedAttach::new(id1, MediaType::Any);
+        let attach2 = RevealedAttach::new(id2, MediaType::Any);
+
+        let status = check_attachment_uniqueness(opid, &[attach1.clone(), attach2.clone()]);
+        assert!(status.warnings.is_empty());
+
+        let duplicate = RevealedAttach::new(id1, MediaType::Any);
+        let status =
+            check_attachment_uniqueness(opid, &[attach1.clone(), attach2, duplicate, attach1]);
+        assert_eq!(status.warnings, vec![validation::Warning::DuplicateAttachment {
+            opid,
+            id: id1
+        }]);
+    }
+
+    #[test]
+    fn blinding_uniqueness() {
+        let opid = OpId::strict_dumb();
+        let values = [
+            RevealedValue::with(10u64, blinding(1)),
+            RevealedValue::with(20u64, blinding(2)),
+            RevealedValue::with(30u64, blinding(3)),
+        ];
+        assert!(check_blinding_uniqueness(opid, TY, &values)
+            .warnings
+            .is_empty());
+        assert!(check_blinding_uniqueness(opid, TY, &[]).warnings.is_empty());
+
+        let reused = [
+            values[0],
+            values[1],
+            RevealedValue::with(5u64, blinding(2)),
+            RevealedValue::with(7u64, blinding(1)),
+            values[2],
+            RevealedValue::with(9u64, blinding(2)),
+        ];
+        let status = check_blinding_uniqueness(opid, TY, &reused);
+        assert!(status.failures.is_empty());
+        assert_eq!(status.warnings, vec![
+            validation::Warning::ReusedBlindingFactor {
+                opid,
+                state_type: TY,
+                indices: vec![0, 3]
+            },
+            validation::Warning::ReusedBlindingFactor {
+                opid,
+                state_type: TY,
+                indices: vec![1, 2, 5]
+            }
+        ]);
+        assert!(status
+            .warnings
+            .iter()
+            .all(|warning| warning.opid() == Some(opid)));
+    }
+
+    #[test]
+    fn fungible_operation_balance() {
+        let opid = OpId::strict_dumb();
+        let conceal = |revealed: &RevealedValue| {
+            ConcealedValue::from_parts(PedersenCommitment::commit(revealed), RangeProof::default())
+        };
+        let input = RevealedValue::with(100u64, blinding(1));
+        let parts = RevealedValue::split(100, &[40, 60], &input.blinding, &mut thread_rng())
+            .unwrap()
+            .iter()
+            .map(conceal)
+            .collect::<Vec<_>>();
+        let no_proof =
+            validation::Failure::BulletproofsInvalid(opid, TY, RangeProofError::BulletproofsAbsent);
+        let validate = |inputs: &[ConcealedValue], outputs: &[ConcealedValue], issuance, burn| {
+            validate_fungible_operation(opid, TY, inputs, outputs, issuance, burn).failures
+        };
+
+        // balanced operation fails only because of the absent range proofs
+        assert_eq!(validate(&[conceal(&input)], &parts, None, None), vec![no_proof.clone(); 3]);
+        // issuance and burn are accounted for
+        assert_eq!(
+            validate(&[], &[conceal(&input)], Some(conceal(&input).commitment), None),
+            vec![no_proof.clone()]
+        );
+        assert_eq!(
+            validate(&[conceal(&input)], &parts[1..], None, Some(parts[0].commitment)),
+            vec![no_proof.clone(), no_proof.clone()]
+        );
+
+        let unbalanced = [parts[0], conceal(&RevealedValue::with(60u64, blinding(2)))];
+        let mut failures = validate(&[conceal(&input)], &unbalanced, None, None);
+        assert_eq!(failures.remove(0), validation::Failure::BalanceMismatch {
+            opid,
+            state_type: TY
+        });
+        assert_eq!(failures, vec![no_proof.clone(); 3]);
+        assert_eq!(validate(&[], &[conceal(&input)], None, None), vec![
+            validation::Failure::BalanceMismatch {
+                opid,
+                state_type: TY
+            },
+            no_proof
+        ]);
+    }
+
+    #[test]
+    fn fungible_output_count() {
+        let opid = OpId::strict_dumb();
+        let conceal = |revealed: &RevealedValue| {
+            ConcealedValue::from_parts(PedersenCommitment::commit(revealed), RangeProof::default())
+        };
+        let input = conceal(&RevealedValue::with(100u64, blinding(1)));
+        let parts = RevealedValue::split(100, &[10, 20, 30, 40], &blinding(1), &mut thread_rng())
+            .unwrap()
+            .iter()
+            .map(conceal)
+            .collect::<Vec<_>>();
+        let count_failures = |outputs: &[ConcealedValue], burn, policy| {
+            validate_fungible_operation_with(opid, TY, &[input], outputs, None, burn, policy)
+                .failures
+                .into_iter()
+                .filter(|failure| {
+                    matches!(failure, validation::Failure::OutputCountOutOfRange { .. })
+                })
+                .collect::<Vec<_>>()
+        };
+        let out_of_range = |count, bound| validation::Failure::OutputCountOutOfRange {
+            opid,
+            state_type: TY,
+            count,
+            bound,
+        };
+
+        let policy = OutputCountPolicy::default();
+        assert!(count_failures(&[], Some(input.commitment), policy).is_empty());
+        assert!(count_failures(&parts, None, policy).is_empty());
+
+        let policy = OutputCountPolicy {
+            min_outputs: 1,
+            max_outputs: Some(3),
+        };
+        // burning everything without outputs
+        assert_eq!(count_failures(&[], Some(input.commitment), policy), vec![out_of_range(0, 1)]);
+        assert!(count_failures(&parts[..3], Some(parts[3].commitment), policy).is_empty());
+        assert_eq!(count_failures(&parts, None, policy), vec![out_of_range(4, 3)]);
+    }
+
+    #[test]
+    fn decode_state_data() {
+        use strict_encoding::{StrictEncode, StrictWriter};
+
+        fn encode(val: &impl StrictEncode) -> Vec<u8> {
+            val.strict_encode(StrictWriter::in_memory(usize::MAX))
+                .unwrap()
+                .unbox()
+        }
+
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Small>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let sem_id = *sys.resolve("Test.Small").unwrap();
+        let type_system = sys.into_type_system();
+
+        let declarative = StateSchema::Declarative;
+        let fungible = StateSchema::fungible(FungibleType::Unsigned64Bit);
+        let structured = StateSchema::Structured(sem_id);
+        let attachment = StateSchema::Attachment(MediaType::Any);
+
+        // Encoded fungible state has the same length as an attachment, so the
+        // blinding byte at the position of the attachment media type must not
+        // be a valid media type tag
+        let value = RevealedValue::with(5u64, blinding(2));
+        let attach = RevealedAttach::new(AttachId::strict_dumb(), MediaType::Any);
+        let value_data = encode(&value);
+        let attach_data = encode(&attach);
+
+        assert_eq!(declarative.decode_state_data(&type_system, &[]).unwrap(), StateData::Void);
+        assert_eq!(
+            fungible
+                .decode_state_data(&type_system, &value_data)
+                .unwrap(),
+            StateData::Fungible(value)
+        );
+        assert_eq!(
+            structured.decode_state_data(&type_system, &[7]).unwrap(),
+            StateData::Structured(RevealedData::from(SmallVec::try_from(vec![7u8]).unwrap()))
+        );
+        assert_eq!(
+            attachment
+                .decode_state_data(&type_system, &attach_data)
+                .unwrap(),
+            StateData::Attachment(attach)
+        );
+
+        // state of a different type
+        assert!(declarative.decode_state_data(&type_system, &[7]).is_err());
+        assert!(fungible
+            .decode_state_data(&type_system, &attach_data)
+            .is_err());
+        assert!(structured
+            .decode_state_data(&type_system, &value_data)
+            .is_err());
+        assert!(structured.decode_state_data(&type_system, &[]).is_err());
+        assert!(attachment
+            .decode_state_data(&type_system, &value_data)
+            .is_err());
+
+        // trailing data
+        let mut extended = value_data.clone();
+        extended.push(0);
+        assert!(fungible.decode_state_data(&type_system, &extended).is_err());
+    }
+
+    #[test]
+    fn structured_constraint() {
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Small>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let sem_id = *sys.resolve("Test.Small").unwrap();
+        let type_system = sys.into_type_system();
+
+        let schema = StateSchema::Structured(sem_id);
+        let opid = OpId::strict_dumb();
+        let data = RevealedData::from(SmallVec::try_from(vec![7u8]).unwrap());
+        let assign = Assign::revealed(GraphSeal::strict_dumb(), data);
+
+        let status = schema.validate(&type_system, &opid, TY, &assign);
+        assert!(status.failures.is_empty());
+
+        let accept = |ty: AssignmentType, id: SemId, _: &StrictVal| ty == TY && id == sem_id;
+        let status = schema.validate_constrained(&type_system, &opid, TY, &assign, Some(&accept));
+        assert!(status.failures.is_empty());
+
+        let reject = |_: AssignmentType, _: SemId, _: &StrictVal| false;
+        let status = schema.validate_constrained(&type_system, &opid, TY, &assign, Some(&reject));
+        assert_eq!(status.failures, vec![
+            validation::Failure::SchemaOwnedValueConstraintViolated {
+                opid,
+                state_type: TY,
+                sem_id
+            }
+        ]);
+
+        let invalid = RevealedData::from(SmallVec::try_from(vec![7u8, 8]).unwrap());
+        let assign = Assign::revealed(GraphSeal::strict_dumb(), invalid);
+        let status = schema.validate_constrained(&type_system, &opid, TY, &assign, Some(&reject));
+        assert_eq!(status.failures, vec![validation::Failure::StructuredStateLengthMismatch {
+            opid,
+            state_type: TY,
+            sem_id,
+            expected: 1,
+            found: 2,
+        }]);
+    }
+
+    #[test]
+    fn structured_value_check() {
+        use strict_types::value::StrictNum;
+
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Small>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let sem_id = *sys.resolve("Test.Small").unwrap();
+        let type_system = sys.into_type_system();
+
+        let schema = StateSchema::Structured(sem_id);
+        let opid = OpId::strict_dumb();
+        let multiple_of_five = StructuredValueCheck(|value: &StrictVal| match value {
+            StrictVal::Tuple(fields) => match fields.as_slice() {
+                [StrictVal::Number(StrictNum::Uint(n))] if n % 5 == 0 => Ok(()),
+                [StrictVal::Number(StrictNum::Uint(n))] => Err(format!("{n} is not multiple of 5")),
+                _ => Err(s!("unexpected value")),
+            },
+            _ => Err(s!("unexpected value")),
+        });
+        let assign = |value: u8| {
+            Assign::revealed(
+                GraphSeal::strict_dumb(),
+                RevealedData::from(SmallVec::try_from(vec![value]).unwrap()),
+            )
+        };
+
+        let status = schema.validate_constrained(
+            &type_system,
+            &opid,
+            TY,
+            &assign(15),
+            Some(&multiple_of_five),
+        );
+        assert!(status.failures.is_empty());
+
+        let status = schema.validate_constrained(
+            &type_system,
+            &opid,
+            TY,
+            &assign(7),
+            Some(&multiple_of_five),
+        );
+        assert_eq!(status.failures, vec![validation::Failure::StructuredValueRejected {
+            opid,
+            state_type: TY,
+            reason: s!("7 is not multiple of 5"),
+        }]);
+        assert!(schema
+            .validate(&type_system, &opid, TY, &assign(7))
+            .failures
+            .is_empty());
+
+        let concealed = Assign::<RevealedData, GraphSeal>::ConfidentialState {
+            seal: GraphSeal::strict_dumb(),
+            state: RevealedData::from(SmallVec::try_from(vec![7u8]).unwrap()).conceal(),
+        };
+        let status = schema.validate_constrained(
+            &type_system,
+            &opid,
+            TY,
+            &concealed,
+            Some(&multiple_of_five),
+        );
+        assert!(status.failures.is_empty());
+    }
+
+    #[test]
+    fn structured_length_precheck() {
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Pair>()
+            .transpile::<Optional>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let pair_id = *sys.resolve("Test.Pair").unwrap();
+        let optional_id = *sys.resolve("Test.Optional").unwrap();
+        let type_system = sys.into_type_system();
+        assert_eq!(fixed_encoded_len(&type_system, pair_id), Some(3));
+        assert_eq!(fixed_encoded_len(&type_system, optional_id), None);
+
+        let opid = OpId::strict_dumb();
+        let validate = |sem_id: SemId, data: Vec<u8>| {
+            let data = RevealedData::from(SmallVec::try_from(data).unwrap());
+            let assign = Assign::revealed(GraphSeal::strict_dumb(), data);
+            StateSchema::Structured(sem_id)
+                .validate(&type_system, &opid, TY, &assign)
+                .failures
+        };
+        let mismatch = |found: usize| {
+            vec![validation::Failure::StructuredStateLengthMismatch {
+                opid,
+                state_type: TY,
+                sem_id: pair_id,
+                expected: 3,
+                found,
+            }]
+        };
+
+        assert!(validate(pair_id, vec![1, 2, 3]).is_empty());
+        assert_eq!(validate(pair_id, vec![1, 2]), mismatch(2));
+        assert_eq!(validate(pair_id, vec![1, 2, 3, 4]), mismatch(4));
+
+        // variable-length types fall back to the full deserialization
+        assert!(validate(optional_id, vec![1, 0]).is_empty());
+        assert!(validate(optional_id, vec![1, 1, 2]).is_empty());
+        assert_eq!(validate(optional_id, vec![1]), vec![
+            validation::Failure::SchemaInvalidOwnedValue(opid, TY, optional_id)
+        ]);
+    }
+
+    #[test]
+    fn structured_non_canonical() {
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Bag>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let sem_id = *sys.resolve("Test.Bag").unwrap();
+        let type_system = sys.into_type_system();
+
+        let opid = OpId::strict_dumb();
+        let validate = |data: Vec<u8>| {
+            let data = RevealedData::from(SmallVec::try_from(data).unwrap());
+            let assign = Assign::revealed(GraphSeal::strict_dumb(), data);
+            StateSchema::Structured(sem_id)
+                .validate(&type_system, &opid, TY, &assign)
+                .failures
+        };
+
+        assert!(validate(vec![0]).is_empty());
+        assert!(validate(vec![2, 1, 5]).is_empty());
+        // the decoder accepts repeated set values, but the encoding is not
+        // canonical
+        assert!(type_system
+            .strict_deserialize_type(sem_id, &[2, 5, 5])
+            .is_ok());
+        assert_eq!(validate(vec![2, 5, 5]), vec![
+            validation::Failure::NonCanonicalStructuredState {
+                opid,
+                state_type: TY,
+                sem_id
+            }
+        ]);
+        assert_eq!(validate(vec![2, 5]), vec![validation::Failure::SchemaInvalidOwnedValue(
+            opid, TY, sem_id
+        )]);
+    }
+
+    #[test]
+    fn structured_length_limit() {
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Optional>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let sem_id = *sys.resolve("Test.Optional").unwrap();
+        let type_system = sys.into_type_system();
+
+        let opid = OpId::strict_dumb();
+        let validate = |data: Vec<u8>, limit: StructuredLenLimit| {
+            let data = RevealedData::from(SmallVec::try_from(data).unwrap());
+            let assign = Assign::revealed(GraphSeal::strict_dumb(), data);
+            StateSchema::Structured(sem_id).validate_with_limit(
+                &type_system,
+                &opid,
+                TY,
+                &assign,
+                None,
+                limit,
+            )
+        };
+
+        for limit in
+            [StructuredLenLimit::None, StructuredLenLimit::Warn(3), StructuredLenLimit::Deny(3)]
+        {
+            assert!(validate(vec![1, 1, 2], limit).warnings.is_empty());
+            assert!(validate(vec![1, 1, 2], limit).failures.is_empty());
+        }
+
+        let status = validate(vec![1, 1, 2], StructuredLenLimit::Warn(2));
+        assert!(status.failures.is_empty());
+        assert_eq!(status.warnings, vec![validation::Warning::LargeStructuredState {
+            opid,
+            state_type: TY,
+            len: 3
+        }]);
+
+        // the state is not deserialized, so no other failure is reported
+        for data in [vec![1, 1, 2], vec![0xFF, 0xFF, 0xFF]] {
+            let status = validate(data, StructuredLenLimit::Deny(2));
+            assert!(status.warnings.is_empty());
+            assert_eq!(status.failures, vec![validation::Failure::StructuredStateTooLarge {
+                opid,
+                state_type: TY,
+                len: 3
+            }]);
+        }
+    }
+
+    #[test]
+    fn fungible_history() {
+        use crate::{
+            AssignFungible, AssignRights, Genesis, GenesisSeal, Input, Inputs, OpRef, Transition,
+            TypedAssigns, VoidState,
+        };
+
+        // Ownership rights, which are spent alongside the fungible state
+        const RIGHTS: AssignmentType = AssignmentType::with(1);
+        // Fungible state type which is only spent by the transitions
+        const BURNED: AssignmentType = AssignmentType::with(2);
+
+        fn transition(inputs: &[Opout], outputs: Vec<AssignFungible<GraphSeal>>) -> Transition {
+            let mut transition = Transition::strict_dumb();
+            transition.inputs = Inputs::from(
+                TinyOrdSet::try_from_iter(inputs.iter().copied().map(Input::with)).unwrap(),
+            );
+            transition
+                .assignments
+                .insert(TY, TypedAssigns::Fungible(SmallVec::try_from(outputs).unwrap()))
+                .unwrap();
+            transition
+        }
+
+        let seal = GraphSeal::strict_dumb();
+        let mut genesis = Genesis::strict_dumb();
+        genesis
+            .assignments
+            .insert(
+                TY,
+                TypedAssigns::Fungible(
+                    SmallVec::try_from_iter([
+                        Assign::revealed(
+                            GenesisSeal::strict_dumb(),
+                            RevealedValue::with(100u64, blinding(1)),
+                        ),
+                        Assign::ConfidentialState {
+                            seal: GenesisSeal::strict_dumb(),
+                            state: ConcealedValue::public(10),
+                        },
+                    ])
+                    .unwrap(),
+                ),
+            )
+            .unwrap();
+        genesis
+            .assignments
+            .insert(
+                RIGHTS,
+                TypedAssigns::Declarative(
+                    SmallVec::try_from_iter([AssignRights::revealed(
+                        GenesisSeal::strict_dumb(),
+                        VoidState::default(),
+                    )])
+                    .unwrap(),
+                ),
+            )
+            .unwrap();
+        let genesis_id = genesis.id();
+
+        // Valid split of the issued amount
+        let parts = RevealedValue::split(100, &[40, 60], &blinding(1), &mut thread_rng()).unwrap();
+        let split = transition(
+            &[Opout::new(genesis_id, TY, 0)],
+            parts
+                .iter()
+                .map(|value| Assign::revealed(seal, *value))
+                .collect(),
+        );
+        let split_id = split.id();
+
+        // Output value exceeds the input value
+        let inflation = transition(&[Opout::new(split_id, TY, 0)], vec![Assign::revealed(
+            seal,
+            RevealedValue::with(50u64, parts[0].blinding),
+        )]);
+        // Input is not a part of the history
+        let unresolved =
+            transition(&[Opout::new(OpId::strict_dumb(), TY, 0)], vec![Assign::revealed(
+                seal,
+                RevealedValue::with(5u64, blinding(2)),
+            )]);
+        // Balanced confidential output with a placeholder range proof
+        let confidential =
+            transition(&[Opout::new(split_id, TY, 1)], vec![Assign::ConfidentialState {
+                seal,
+                state: ConcealedValue::from_parts(
+                    PedersenCommitment::commit(&parts[1]),
+                    RangeProof::default(),
+                ),
+            }]);
+        // Public amounts don't require range proofs; spent rights don't
+        // affect the balance
+        let public =
+            transition(&[Opout::new(genesis_id, TY, 1), Opout::new(genesis_id, RIGHTS, 0)], vec![
+                Assign::ConfidentialState {
+                    seal,
+                    state: ConcealedValue::public(10),
+                },
+            ]);
+        // Input of a type without outputs in the transition is not a part of
+        // the history
+        let input_only = transition(&[Opout::new(OpId::strict_dumb(), BURNED, 0)], vec![]);
+
+        let ops = [
+            OpRef::Genesis(&genesis),
+            OpRef::Transition(&split),
+            OpRef::Transition(&inflation),
+            OpRef::Transition(&unresolved),
+            OpRef::Transition(&confidential),
+            OpRef::Transition(&public),
+            OpRef::Transition(&input_only),
+        ];
+        let status = validate_fungible_history(&ops);
+        assert!(status.warnings.is_empty());
+        assert_eq!(status.failures, vec![
+            validation::Failure::BalanceMismatch {
+                opid: inflation.id(),
+                state_type: TY,
+            },
+            validation::Failure::NoPrevOut(unresolved.id(), Opout::new(OpId::strict_dumb(), TY, 0)),
+            validation::Failure::BulletproofsInvalid(
+                confidential.id(),
+                TY,
+                RangeProofError::BulletproofsAbsent
+            ),
+            validation::Failure::NoPrevOut(
+                input_only.id(),
+                Opout::new(OpId::strict_dumb(), BURNED, 0)
+            ),
+        ]);
+
+        let mut reversed = ops;
+        reversed.reverse();
+        assert_eq!(validate_fungible_history(&reversed).normalized(), status.clone().normalized());
+
+        assert!(validate_fungible_history(&ops[..2]).failures.is_empty());
+        assert!(validate_fungible_history::<OpRef>(&[]).failures.is_empty());
+    }
+
+    // Run both with and without the `rayon` feature: the parallel validation
+    // must produce exactly the same status as the sequential one.
+    #[test]
+    fn fungible_history_order() {
+        use crate::{AssignFungible, Input, Inputs, Transition, TypedAssigns};
+
+        let ops = (0..64u16)
+            .map(|no| {
+                let mut transition = Transition::strict_dumb();
+                transition.inputs = Inputs::from(
+                    TinyOrdSet::try_from_iter([Input::with(Opout::new(
+                        OpId::strict_dumb(),
+                        TY,
+                        no,
+                    ))])
+                    .unwrap(),
+                );
+                let outputs = [AssignFungible::ConfidentialState {
+                    seal: GraphSeal::strict_dumb(),
+                    state: ConcealedValue::from_parts(
+                        PedersenCommitment::commit(&RevealedValue::with(no as u64, blinding(1))),
+                        RangeProof::default(),
+                    ),
+                }];
+                transition
+                    .assignments
+                    .insert(TY, TypedAssigns::Fungible(SmallVec::try_from_iter(outputs).unwrap()))
+                    .unwrap();
+                transition
+            })
+            .collect::<Vec<_>>();
+
+        let status = validate_fungible_history(&ops);
+        let sequential = ops
+            .iter()
+            .fold(validation::Status::new(), |mut status, op| {
+                status += validate_fungible_history(std::slice::from_ref(op));
+                status
+            });
+        assert_eq!(status, sequential);
+        let expected = ops
+            .iter()
+            .enumerate()
+            .flat_map(|(no, op)| {
+                [
+                    validation::Failure::BulletproofsInvalid(
+                        op.id(),
+                        TY,
+                        RangeProofError::BulletproofsAbsent,
+                    ),
+                    validation::Failure::NoPrevOut(
+                        op.id(),
+                        Opout::new(OpId::strict_dumb(), TY, no as u16),
+                    ),
+                ]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(status.failures, expected);
+    }
 }
@@ -20,8 +20,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use core::hash::{Hash, Hasher};
 use core::iter::FromIterator;
+use core::mem;
 use core::ops::AddAssign;
+use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
 
 use bp::dbc::anchor;
@@ -31,7 +34,8 @@ use strict_types::SemId;
 use crate::contract::Opout;
 use crate::schema::{self, SchemaId};
 use crate::{
-    AssignmentType, BundleId, OccurrencesMismatch, OpFullType, OpId, SecretSeal, StateType,
+    AssignmentType, AttachId, BundleId, OccurrencesMismatch, OpFullType, OpId, RangeProofError,
+    SecretSeal, StateType,
 };
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Display)]
@@ -50,6 +54,30 @@ pub enum Validity {
     Invalid,
 }
 
+/// Overall severity of the validation status entries.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum Severity {
+    /// There are neither failures nor warnings.
+    Valid,
+
+    /// There are no failures, but there are warnings or non-mined terminals.
+    Warned,
+
+    /// There is at least one failure.
+    Failed,
+}
+
+/// Number of entries of each kind in a validation [`Status`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct StatusSummary {
+    pub failures: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    /// Whether the status validity is [`Validity::Valid`].
+    pub valid: bool,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 #[cfg_attr(
     feature = "serde",
@@ -66,7 +94,16 @@ pub struct Status {
 
 impl Display for Status {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let summary = self.summary();
         writeln!(f, "Consignment {}", self.validity())?;
+        writeln!(
+            f,
+            "Severity: {} ({} failure(s), {} warning(s), {} info)",
+            self.severity(),
+            summary.failures,
+            summary.warnings,
+            summary.infos
+        )?;
 
         if !self.unresolved_txids.is_empty() {
             f.write_str("Unknown witness transactions:\n")?;
@@ -163,6 +200,94 @@ impl Status {
         self
     }
 
+    /// Returns status with all its entries sorted into a canonical order, such
+    /// that validating the same data in a different order produces equal
+    /// normalized statuses.
+    ///
+    /// Transaction ids are sorted in their natural order. Failures, warnings
+    /// and info entries are sorted by the id of the operation they refer to,
+    /// then by the owned state type; entries not related to a specific
+    /// operation or state type go before the ones which are. Warnings and
+    /// info entries with the same operation and state type are further
+    /// ordered by their variant in the order of declaration and then by the
+    /// variant data. Failures may contain errors from other libraries which
+    /// have no ordering, so they are further ordered by their description.
+    pub fn normalized(mut self) -> Status {
+        self.unresolved_txids.sort();
+        self.unmined_terminals.sort();
+        self.failures
+            .sort_by_cached_key(|f| (f.opid(), f.state_type(), f.to_string()));
+        self.warnings
+            .sort_by(|a, b| (a.opid(), a.state_type(), a).cmp(&(b.opid(), b.state_type(), b)));
+        self.info
+            .sort_by(|a, b| (a.opid(), a.state_type(), a).cmp(&(b.opid(), b.state_type(), b)));
+        self
+    }
+
+    /// Counts status entries of each kind.
+    pub fn summary(&self) -> StatusSummary {
+        StatusSummary {
+            failures: self.failures.len(),
+            warnings: self.warnings.len(),
+            infos: self.info.len(),
+            valid: self.validity() == Validity::Valid,
+        }
+    }
+
+    /// Iterates over failures referring to the operation with the given
+    /// `opid`, in the order they were reported.
+    pub fn failures_for(&self, opid: OpId) -> impl Iterator<Item = &Failure> {
+        self.failures
+            .iter()
+            .filter(move |failure| failure.opid() == Some(opid))
+    }
+
+    /// Iterates over warnings referring to the operation with the given
+    /// `opid`, in the order they were reported.
+    pub fn warnings_for(&self, opid: OpId) -> impl Iterator<Item = &Warning> {
+        self.warnings
+            .iter()
+            .filter(move |warning| warning.opid() == Some(opid))
+    }
+
+    /// Iterates over info entries referring to the operation with the given
+    /// `opid`, in the order they were reported.
+    pub fn infos_for(&self, opid: OpId) -> impl Iterator<Item = &Info> {
+        self.info
+            .iter()
+            .filter(move |info| info.opid() == Some(opid))
+    }
+
+    /// Compares this status with the `other` status, for instance produced by
+    /// a different version of the library for the same consignment, reporting
+    /// failures and warnings present only in one of them.
+    ///
+    /// Entries are compared as multisets, so an entry reported twice in one
+    /// status and once in the other is listed in the diff once. The order of
+    /// the entries doesn't matter.
+    pub fn compare(&self, other: &Status) -> StatusDiff {
+        let (failures_removed, failures_added) = multiset_diff(&self.failures, &other.failures);
+        let (warnings_removed, warnings_added) = multiset_diff(&self.warnings, &other.warnings);
+        StatusDiff {
+            failures_added,
+            failures_removed,
+            warnings_added,
+            warnings_removed,
+        }
+    }
+
+    /// Returns the most severe kind of entries present in the status. Info
+    /// entries do not affect the severity.
+    pub fn severity(&self) -> Severity {
+        if !self.failures.is_empty() {
+            Severity::Failed
+        } else if !self.warnings.is_empty() || !self.unmined_terminals.is_empty() {
+            Severity::Warned
+        } else {
+            Severity::Valid
+        }
+    }
+
     pub fn validity(&self) -> Validity {
         if self.failures.is_empty() {
             if self.unmined_terminals.is_empty() {
@@ -178,6 +303,75 @@ impl Status {
     }
 }
 
+/// Returns entries present only in `old` and only in `new`, treating both as
+/// multisets and preserving the original order of the entries.
+fn multiset_diff<T: Clone + Eq + Hash>(old: &[T], new: &[T]) -> (Vec<T>, Vec<T>) {
+    fn subtract<T: Clone + Eq + Hash>(from: &[T], what: &[T]) -> Vec<T> {
+        let mut counts = HashMap::<&T, usize>::new();
+        for item in what {
+            *counts.entry(item).or_default() += 1;
+        }
+        from.iter()
+            .filter(|item| match counts.get_mut(item) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .cloned()
+            .collect()
+    }
+    (subtract(old, new), subtract(new, old))
+}
+
+/// Differences between two validation statuses, see [`Status::compare`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct StatusDiff {
+    /// Failures present only in the other status.
+    pub failures_added: Vec<Failure>,
+
+    /// Failures present only in the original status.
+    pub failures_removed: Vec<Failure>,
+
+    /// Warnings present only in the other status.
+    pub warnings_added: Vec<Warning>,
+
+    /// Warnings present only in the original status.
+    pub warnings_removed: Vec<Warning>,
+}
+
+impl StatusDiff {
+    /// Detects whether both statuses have the same failures and warnings.
+    pub fn is_empty(&self) -> bool {
+        self.failures_added.is_empty() &&
+            self.failures_removed.is_empty() &&
+            self.warnings_added.is_empty() &&
+            self.warnings_removed.is_empty()
+    }
+}
+
+impl Display for StatusDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no changes\n");
+        }
+        for failure in &self.failures_added {
+            writeln!(f, "+ failure: {failure}")?;
+        }
+        for failure in &self.failures_removed {
+            writeln!(f, "- failure: {failure}")?;
+        }
+        for warning in &self.warnings_added {
+            writeln!(f, "+ warning: {warning}")?;
+        }
+        for warning in &self.warnings_removed {
+            writeln!(f, "- warning: {warning}")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Display, From)]
 #[cfg_attr(
     feature = "serde",
@@ -197,6 +391,9 @@ pub enum Failure {
     },
     /// schema uses reserved type for the blank state transition.
     SchemaBlankTransitionRedefined,
+    /// schema requires genesis validation, but its script doesn't define a
+    /// validator for the genesis entry point.
+    MissingGenesisValidator,
 
     /// schema global state #{0} uses semantic data type absent in type library
     /// ({1}).
@@ -281,6 +478,44 @@ pub enum Failure {
     /// invalid owned state value in operation {0}, state type #{1} which does
     /// not match semantic type id {2}.
     SchemaInvalidOwnedValue(OpId, schema::AssignmentType, SemId),
+    /// owned state value in operation {opid}, state type #{state_type} matches
+    /// semantic type id {sem_id}, but violates custom schema constraint on
+    /// its value.
+    SchemaOwnedValueConstraintViolated {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        sem_id: SemId,
+    },
+    /// owned state value in operation {opid}, state type #{state_type} is
+    /// rejected by the schema value check: {reason}.
+    StructuredValueRejected {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        reason: String,
+    },
+    /// owned state value in operation {opid}, state type #{state_type} has
+    /// {found} bytes, while semantic type id {sem_id} requires exactly
+    /// {expected} bytes.
+    StructuredStateLengthMismatch {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        sem_id: SemId,
+        expected: usize,
+        found: usize,
+    },
+    /// owned state value in operation {opid}, state type #{state_type} is not
+    /// in the canonical strict encoding of semantic type {sem_id}.
+    NonCanonicalStructuredState {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        sem_id: SemId,
+    },
+    /// operation {opid} has state of assignment type #{state_type}, which is
+    /// not declared by the schema.
+    AssignmentTypeNotInSchema {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+    },
     /// invalid number of input entries of type {1} in operation {0} - {2}  
     SchemaInputOccurrences(OpId, schema::AssignmentType, OccurrencesMismatch),
     /// invalid number of assignment entries of type {1} in operation {0} - {2}
@@ -340,6 +575,13 @@ pub enum Failure {
     },
 
     // Data check errors
+    /// structured state in {opid}/{state_type} has {len} bytes, which
+    /// exceeds the limit set by the validator.
+    StructuredStateTooLarge {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        len: usize,
+    },
     /// state in {opid}/{state_type} is of {found} type, while schema requires
     /// it to be {expected}.
     StateTypeMismatch {
@@ -356,6 +598,14 @@ pub enum Failure {
         expected: schema::MediaType,
         found: schema::MediaType,
     },
+    /// state in {opid}/{state_type} is of {found} type, which is not any of
+    /// the types allowed by the schema.
+    MediaTypeNotAllowed {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        allowed: Vec<schema::MediaType>,
+        found: schema::MediaType,
+    },
     /// state in {opid}/{state_type} is of {found} type, while schema requires
     /// it to be {expected}.
     FungibleTypeMismatch {
@@ -364,8 +614,29 @@ pub enum Failure {
         expected: schema::FungibleType,
         found: schema::FungibleType,
     },
+    /// state in {opid}/{state_type} is confidential, while schema requires
+    /// fungible state of this type to be public.
+    ConfidentialityNotAllowed {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+    },
     /// invalid bulletproofs in {0}:{1}: {2}
-    BulletproofsInvalid(OpId, u16, String),
+    BulletproofsInvalid(OpId, schema::AssignmentType, RangeProofError),
+    /// sum of fungible state commitments in {opid}/{state_type} inputs
+    /// doesn't match the sum of its outputs.
+    BalanceMismatch {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+    },
+    /// operation {opid} has {count} fungible outputs of state type
+    /// #{state_type}, violating the bound of {bound} outputs required by the
+    /// validation policy.
+    OutputCountOutOfRange {
+        opid: OpId,
+        state_type: schema::AssignmentType,
+        count: usize,
+        bound: usize,
+    },
     /// operation {0} is invalid: {1}
     ScriptFailure(OpId, String),
 
@@ -374,7 +645,157 @@ pub enum Failure {
     Custom(String),
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Display, From)]
+// Errors of the seal and anchor verification do not implement `Hash`, so we
+// hash only the variant together with the operation and state type it refers
+// to, which is consistent with the derived equality.
+impl Hash for Failure {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        self.opid().hash(state);
+        self.state_type().hash(state);
+    }
+}
+
+impl Failure {
+    /// Returns id of the operation the failure refers to, or `None` if the
+    /// failure is not specific to a single operation.
+    pub fn opid(&self) -> Option<OpId> {
+        match self {
+            Failure::SchemaUnknownExtensionType(opid, _) |
+            Failure::SchemaUnknownTransitionType(opid, _) |
+            Failure::SchemaUnknownGlobalStateType(opid, _) |
+            Failure::SchemaUnknownAssignmentType(opid, _) |
+            Failure::SchemaUnknownValencyType(opid, _) |
+            Failure::SchemaGlobalStateOccurrences(opid, _, _) |
+            Failure::SchemaGlobalStateLimit(opid, _, _, _) |
+            Failure::SchemaInvalidMetadata(opid, _) |
+            Failure::SchemaInvalidGlobalValue(opid, _, _) |
+            Failure::SchemaInvalidOwnedValue(opid, _, _) |
+            Failure::SchemaOwnedValueConstraintViolated { opid, .. } |
+            Failure::StructuredValueRejected { opid, .. } |
+            Failure::StructuredStateLengthMismatch { opid, .. } |
+            Failure::NonCanonicalStructuredState { opid, .. } |
+            Failure::AssignmentTypeNotInSchema { opid, .. } |
+            Failure::StructuredStateTooLarge { opid, .. } |
+            Failure::SchemaInputOccurrences(opid, _, _) |
+            Failure::SchemaAssignmentOccurrences(opid, _, _) |
+            Failure::OperationAbsent(opid) |
+            Failure::TransitionAbsent(opid) |
+            Failure::NotAnchored(opid) |
+            Failure::NotInAnchor(opid, _) |
+            Failure::NoPrevState { opid, .. } |
+            Failure::NoPrevOut(opid, _) |
+            Failure::MpcInvalid(opid, _) |
+            Failure::SealInvalid(opid, _, _) |
+            Failure::AnchorInvalid(opid, _, _) |
+            Failure::ValencyNoParent { opid, .. } |
+            Failure::NoPrevValency { opid, .. } |
+            Failure::StateTypeMismatch { opid, .. } |
+            Failure::MediaTypeMismatch { opid, .. } |
+            Failure::MediaTypeNotAllowed { opid, .. } |
+            Failure::FungibleTypeMismatch { opid, .. } |
+            Failure::ConfidentialityNotAllowed { opid, .. } |
+            Failure::BulletproofsInvalid(opid, _, _) |
+            Failure::BalanceMismatch { opid, .. } |
+            Failure::OutputCountOutOfRange { opid, .. } |
+            Failure::ScriptFailure(opid, _) => Some(*opid),
+            Failure::ConfidentialSeal(opout) => Some(opout.op),
+            Failure::SchemaMismatch { .. } |
+            Failure::SchemaBlankTransitionRedefined |
+            Failure::MissingGenesisValidator |
+            Failure::SchemaGlobalSemIdUnknown(_, _) |
+            Failure::SchemaOwnedSemIdUnknown(_, _) |
+            Failure::SchemaOpMetaSemIdUnknown(_, _) |
+            Failure::SchemaOpEmptyInputs(_) |
+            Failure::SchemaOpGlobalTypeUnknown(_, _) |
+            Failure::SchemaOpAssignmentTypeUnknown(_, _) |
+            Failure::SchemaOpValencyTypeUnknown(_, _) |
+            Failure::SubschemaGlobalStateMismatch(_) |
+            Failure::SubschemaAssignmentTypeMismatch(_) |
+            Failure::SubschemaValencyTypeMismatch(_) |
+            Failure::SubschemaTransitionTypeMismatch(_) |
+            Failure::SubschemaExtensionTypeMismatch(_) |
+            Failure::SubschemaOpMetaMismatch { .. } |
+            Failure::SubschemaOpGlobalStateMismatch(_, _) |
+            Failure::SubschemaOpInputMismatch(_, _) |
+            Failure::SubschemaOpRedeemMismatch(_, _) |
+            Failure::SubschemaOpAssignmentsMismatch(_, _) |
+            Failure::SubschemaOpValencyMismatch(_, _) |
+            Failure::BundleInvalid(_) |
+            Failure::SealNoWitnessTx(_) |
+            Failure::Custom(_) => None,
+        }
+    }
+
+    fn state_type(&self) -> Option<AssignmentType> {
+        match self {
+            Failure::SchemaOwnedSemIdUnknown(state_type, _) |
+            Failure::SchemaOpAssignmentTypeUnknown(_, state_type) |
+            Failure::SubschemaAssignmentTypeMismatch(state_type) |
+            Failure::SubschemaOpInputMismatch(_, state_type) |
+            Failure::SubschemaOpAssignmentsMismatch(_, state_type) |
+            Failure::SchemaUnknownAssignmentType(_, state_type) |
+            Failure::SchemaInvalidOwnedValue(_, state_type, _) |
+            Failure::SchemaOwnedValueConstraintViolated { state_type, .. } |
+            Failure::StructuredValueRejected { state_type, .. } |
+            Failure::StructuredStateLengthMismatch { state_type, .. } |
+            Failure::NonCanonicalStructuredState { state_type, .. } |
+            Failure::AssignmentTypeNotInSchema { state_type, .. } |
+            Failure::StructuredStateTooLarge { state_type, .. } |
+            Failure::SchemaInputOccurrences(_, state_type, _) |
+            Failure::SchemaAssignmentOccurrences(_, state_type, _) |
+            Failure::NoPrevState { state_type, .. } |
+            Failure::StateTypeMismatch { state_type, .. } |
+            Failure::MediaTypeMismatch { state_type, .. } |
+            Failure::MediaTypeNotAllowed { state_type, .. } |
+            Failure::FungibleTypeMismatch { state_type, .. } |
+            Failure::ConfidentialityNotAllowed { state_type, .. } |
+            Failure::BulletproofsInvalid(_, state_type, _) |
+            Failure::BalanceMismatch { state_type, .. } |
+            Failure::OutputCountOutOfRange { state_type, .. } => Some(*state_type),
+            Failure::NoPrevOut(_, opout) | Failure::ConfidentialSeal(opout) => Some(opout.ty),
+            Failure::SchemaMismatch { .. } |
+            Failure::SchemaBlankTransitionRedefined |
+            Failure::MissingGenesisValidator |
+            Failure::SchemaGlobalSemIdUnknown(_, _) |
+            Failure::SchemaOpMetaSemIdUnknown(_, _) |
+            Failure::SchemaOpEmptyInputs(_) |
+            Failure::SchemaOpGlobalTypeUnknown(_, _) |
+            Failure::SchemaOpValencyTypeUnknown(_, _) |
+            Failure::SubschemaGlobalStateMismatch(_) |
+            Failure::SubschemaValencyTypeMismatch(_) |
+            Failure::SubschemaTransitionTypeMismatch(_) |
+            Failure::SubschemaExtensionTypeMismatch(_) |
+            Failure::SubschemaOpMetaMismatch { .. } |
+            Failure::SubschemaOpGlobalStateMismatch(_, _) |
+            Failure::SubschemaOpRedeemMismatch(_, _) |
+            Failure::SubschemaOpValencyMismatch(_, _) |
+            Failure::SchemaUnknownExtensionType(_, _) |
+            Failure::SchemaUnknownTransitionType(_, _) |
+            Failure::SchemaUnknownGlobalStateType(_, _) |
+            Failure::SchemaUnknownValencyType(_, _) |
+            Failure::SchemaGlobalStateOccurrences(_, _, _) |
+            Failure::SchemaGlobalStateLimit(_, _, _, _) |
+            Failure::SchemaInvalidMetadata(_, _) |
+            Failure::SchemaInvalidGlobalValue(_, _, _) |
+            Failure::OperationAbsent(_) |
+            Failure::TransitionAbsent(_) |
+            Failure::BundleInvalid(_) |
+            Failure::NotAnchored(_) |
+            Failure::NotInAnchor(_, _) |
+            Failure::MpcInvalid(_, _) |
+            Failure::SealNoWitnessTx(_) |
+            Failure::SealInvalid(_, _, _) |
+            Failure::AnchorInvalid(_, _, _) |
+            Failure::ValencyNoParent { .. } |
+            Failure::NoPrevValency { .. } |
+            Failure::ScriptFailure(_, _) |
+            Failure::Custom(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, From)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -390,13 +811,65 @@ pub enum Warning {
     ExcessiveOperation(OpId),
     /// terminal witness transaction {0} is not yet mined.
     TerminalWitnessNotMined(Txid),
+    /// operation {opid} contains multiple attachments with the same id {id}.
+    DuplicateAttachment { opid: OpId, id: AttachId },
+    /// structured state in {opid}/{state_type} has {len} bytes, which is
+    /// unusually large.
+    LargeStructuredState {
+        opid: OpId,
+        state_type: AssignmentType,
+        len: usize,
+    },
+    /// attachment in {opid}/{state_type} has media type {media_type}, which
+    /// is potentially dangerous to open.
+    PotentiallyDangerousAttachment {
+        opid: OpId,
+        state_type: AssignmentType,
+        media_type: schema::MediaType,
+    },
+    /// fungible values in {opid}/{state_type} at positions {indices:?} share
+    /// the same blinding factor, which leaks the difference of their amounts.
+    ReusedBlindingFactor {
+        opid: OpId,
+        state_type: AssignmentType,
+        indices: Vec<usize>,
+    },
 
     /// Custom warning by external services on top of RGB Core.
     #[display(inner)]
     Custom(String),
 }
 
-#[derive(Clone, PartialEq, Eq, Debug, Display, From)]
+impl Warning {
+    /// Returns id of the operation the warning refers to, or `None` if the
+    /// warning is not specific to a single operation.
+    pub fn opid(&self) -> Option<OpId> {
+        match self {
+            Warning::TerminalSealAbsent(opid, _) |
+            Warning::ExcessiveOperation(opid) |
+            Warning::DuplicateAttachment { opid, .. } |
+            Warning::LargeStructuredState { opid, .. } |
+            Warning::PotentiallyDangerousAttachment { opid, .. } |
+            Warning::ReusedBlindingFactor { opid, .. } => Some(*opid),
+            Warning::TerminalWitnessNotMined(_) | Warning::Custom(_) => None,
+        }
+    }
+
+    fn state_type(&self) -> Option<AssignmentType> {
+        match self {
+            Warning::LargeStructuredState { state_type, .. } |
+            Warning::PotentiallyDangerousAttachment { state_type, .. } |
+            Warning::ReusedBlindingFactor { state_type, .. } => Some(*state_type),
+            Warning::TerminalSealAbsent(_, _) |
+            Warning::ExcessiveOperation(_) |
+            Warning::TerminalWitnessNotMined(_) |
+            Warning::DuplicateAttachment { .. } |
+            Warning::Custom(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Display, From)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -408,7 +881,248 @@ pub enum Info {
     /// thus was not validated.
     UncheckableConfidentialState(OpId, AssignmentType),
 
+    /// operation {opid} contains only confidential state, so none of its
+    /// assignments were validated.
+    OperationFullyConfidential { opid: OpId },
+
     /// Custom info by external services on top of RGB Core.
     #[display(inner)]
     Custom(String),
 }
+
+impl Info {
+    /// Returns id of the operation the info entry refers to, or `None` if the
+    /// entry is not specific to a single operation.
+    pub fn opid(&self) -> Option<OpId> {
+        match self {
+            Info::UncheckableConfidentialState(opid, _) |
+            Info::OperationFullyConfidential { opid } => Some(*opid),
+            Info::Custom(_) => None,
+        }
+    }
+
+    fn state_type(&self) -> Option<AssignmentType> {
+        match self {
+            Info::UncheckableConfidentialState(_, state_type) => Some(*state_type),
+            Info::OperationFullyConfidential { .. } | Info::Custom(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strict_encoding::StrictDumb;
+    use strict_types::TypeSystem;
+
+    use super::*;
+    use crate::{Assign, AttachId, BlindingFactor, GraphSeal, RevealedValue, StateSchema};
+
+    #[test]
+    fn summary_severity() {
+        let opid = OpId::from([1u8; 32]);
+        let mut status = Status::new();
+        assert_eq!(status.summary(), StatusSummary {
+            failures: 0,
+            warnings: 0,
+            infos: 0,
+            valid: true
+        });
+        assert_eq!(status.severity(), Severity::Valid);
+
+        status.add_info(Info::UncheckableConfidentialState(opid, AssignmentType::with(1)));
+        status.add_info(Info::Custom(s!("info")));
+        assert_eq!(status.severity(), Severity::Valid);
+
+        status.add_warning(Warning::ExcessiveOperation(opid));
+        assert_eq!(status.severity(), Severity::Warned);
+        assert!(status.summary().valid);
+
+        status.add_failure(Failure::OperationAbsent(opid));
+        status.add_failure(Failure::Custom(s!("failure")));
+        assert_eq!(status.summary(), StatusSummary {
+            failures: 2,
+            warnings: 1,
+            infos: 2,
+            valid: false
+        });
+        assert_eq!(status.severity(), Severity::Failed);
+        assert_eq!(status.severity().to_string(), "failed");
+
+        let unmined = Status {
+            unmined_terminals: vec![Txid::from([3u8; 32])],
+            ..Status::default()
+        };
+        assert_eq!(unmined.severity(), Severity::Warned);
+        assert!(!unmined.summary().valid);
+    }
+
+    #[test]
+    fn entries_for_opid() {
+        let opid1 = OpId::from([1u8; 32]);
+        let opid2 = OpId::from([2u8; 32]);
+        let state_type = AssignmentType::with(1);
+        let mut status = Status::new();
+        status.add_failure(Failure::OperationAbsent(opid1));
+        status.add_failure(Failure::Custom(s!("failure")));
+        status.add_failure(Failure::BalanceMismatch {
+            opid: opid2,
+            state_type,
+        });
+        status.add_failure(Failure::ScriptFailure(opid1, s!("script")));
+        status.add_warning(Warning::ExcessiveOperation(opid2));
+        status.add_warning(Warning::Custom(s!("warning")));
+        status.add_info(Info::UncheckableConfidentialState(opid1, state_type));
+        status.add_info(Info::OperationFullyConfidential { opid: opid2 });
+
+        assert_eq!(status.failures_for(opid1).collect::<Vec<_>>(), vec![
+            &Failure::OperationAbsent(opid1),
+            &Failure::ScriptFailure(opid1, s!("script"))
+        ]);
+        assert_eq!(status.failures_for(opid2).collect::<Vec<_>>(), vec![
+            &Failure::BalanceMismatch {
+                opid: opid2,
+                state_type
+            }
+        ]);
+        assert_eq!(status.warnings_for(opid1).count(), 0);
+        assert_eq!(status.warnings_for(opid2).collect::<Vec<_>>(), vec![
+            &Warning::ExcessiveOperation(opid2)
+        ]);
+        assert_eq!(status.infos_for(opid1).collect::<Vec<_>>(), vec![
+            &Info::UncheckableConfidentialState(opid1, state_type)
+        ]);
+        assert_eq!(status.infos_for(opid2).collect::<Vec<_>>(), vec![
+            &Info::OperationFullyConfidential { opid: opid2 }
+        ]);
+
+        let unknown = OpId::from([3u8; 32]);
+        assert_eq!(status.failures_for(unknown).count(), 0);
+        assert_eq!(Failure::Custom(s!("failure")).opid(), None);
+        assert_eq!(Warning::Custom(s!("warning")).opid(), None);
+    }
+
+    #[test]
+    fn compare() {
+        let opid = OpId::from([1u8; 32]);
+        let mut old = Status::new();
+        old.add_failure(Failure::OperationAbsent(opid));
+        old.add_failure(Failure::Custom(s!("failure")));
+        old.add_failure(Failure::Custom(s!("failure")));
+        old.add_warning(Warning::ExcessiveOperation(opid));
+        old.add_info(Info::Custom(s!("info")));
+
+        let mut reordered = Status::new();
+        reordered.add_warning(Warning::ExcessiveOperation(opid));
+        reordered.add_failure(Failure::Custom(s!("failure")));
+        reordered.add_failure(Failure::OperationAbsent(opid));
+        reordered.add_failure(Failure::Custom(s!("failure")));
+        let diff = old.compare(&reordered);
+        assert!(diff.is_empty());
+        assert_eq!(diff, StatusDiff::default());
+        assert_eq!(diff.to_string(), "no changes\n");
+        assert!(old.compare(&old).is_empty());
+
+        let mut new = Status::new();
+        new.add_failure(Failure::Custom(s!("failure")));
+        new.add_failure(Failure::ScriptFailure(opid, s!("script")));
+        new.add_warning(Warning::ExcessiveOperation(opid));
+        new.add_warning(Warning::Custom(s!("warning")));
+        let diff = old.compare(&new);
+        assert_eq!(diff, StatusDiff {
+            failures_added: vec![Failure::ScriptFailure(opid, s!("script"))],
+            failures_removed: vec![Failure::OperationAbsent(opid), Failure::Custom(s!("failure"))],
+            warnings_added: vec![Warning::Custom(s!("warning"))],
+            warnings_removed: vec![],
+        });
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "+ failure: {}\n- failure: {}\n- failure: failure\n+ warning: warning\n",
+                Failure::ScriptFailure(opid, s!("script")),
+                Failure::OperationAbsent(opid)
+            )
+        );
+
+        let reverse = new.compare(&old);
+        assert_eq!(reverse.failures_added, diff.failures_removed);
+        assert_eq!(reverse.warnings_removed, diff.warnings_added);
+    }
+
+    #[test]
+    fn display_report() {
+        let mut status = Status::new();
+        status.add_failure(Failure::Custom(s!("invalid state")));
+        status.add_warning(Warning::Custom(s!("excessive data")));
+        assert_eq!(
+            status.to_string(),
+            "Consignment is NOT valid\nSeverity: failed (1 failure(s), 1 warning(s), 0 \
+             info)\nValidation failures:\n- invalid state\nValidation warnings:\n- excessive \
+             data\n"
+        );
+    }
+
+    #[test]
+    fn normalized_order() {
+        let schema = StateSchema::Declarative;
+        let opid1 = OpId::from([1u8; 32]);
+        let opid2 = OpId::from([2u8; 32]);
+        let ty1 = AssignmentType::with(1);
+        let ty2 = AssignmentType::with(2);
+        let fungible = Assign::revealed(
+            GraphSeal::strict_dumb(),
+            RevealedValue::with(10u64, BlindingFactor::try_from([1u8; 32]).unwrap()),
+        );
+        let sys = TypeSystem::new();
+
+        let items = [(opid2, ty1), (opid1, ty2), (opid1, ty1)];
+        let mut forward = Status::new();
+        for (opid, ty) in items {
+            forward += schema.validate(&sys, &opid, ty, &fungible);
+        }
+        let large = |state_type, len| Warning::LargeStructuredState {
+            opid: opid2,
+            state_type,
+            len,
+        };
+        let duplicate = Warning::DuplicateAttachment {
+            opid: opid2,
+            id: AttachId::strict_dumb(),
+        };
+        let warnings = [
+            large(ty2, 1),
+            duplicate.clone(),
+            large(ty1, 2),
+            large(ty1, 1),
+            Warning::ExcessiveOperation(opid2),
+            Warning::TerminalWitnessNotMined(Txid::from([3u8; 32])),
+        ];
+        for warning in warnings.clone() {
+            forward.add_warning(warning);
+        }
+        let mut backward = Status::new();
+        for warning in warnings.into_iter().rev() {
+            backward.add_warning(warning);
+        }
+        for (opid, ty) in items.into_iter().rev() {
+            backward += schema.validate(&sys, &opid, ty, &fungible);
+        }
+        assert_ne!(forward, backward);
+
+        let normalized = forward.normalized();
+        assert_eq!(normalized, backward.normalized());
+        let keys = normalized
+            .failures
+            .iter()
+            .map(|f| (f.opid().unwrap(), f.state_type().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![(opid1, ty1), (opid1, ty2), (opid2, ty1)]);
+        assert_eq!(normalized.warnings, vec![
+            Warning::TerminalWitnessNotMined(Txid::from([3u8; 32])),
+            Warning::ExcessiveOperation(opid2),
+            duplicate,
+            large(ty1, 1),
+            large(ty1, 2),
+            large(ty2, 1),
+        ]);
+    }
+}
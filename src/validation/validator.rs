@@ -450,3 +450,137 @@ impl<'consignment, 'resolver, C: ConsignmentApi, R: ResolveTx>
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::{SmallBlob, SmallVec};
+    use amplify::Wrapper;
+    use strict_encoding::StrictDumb;
+    use strict_types::typelib::{Dependency, LibBuilder};
+    use strict_types::typesys::SystemBuilder;
+
+    use super::*;
+    use crate::schema::{AssignmentType, FungibleType, StateSchema};
+    use crate::{
+        AssignFungible, Assignments, ConcealedValue, Extension, Genesis, GenesisSeal, NoiseDumb,
+        Occurrences, PedersenCommitment, RangeProof, RangeProofError, SecretSeal,
+    };
+
+    const TY: AssignmentType = AssignmentType::with(1);
+
+    struct GenesisOnly {
+        schema: SubSchema,
+        genesis: Genesis,
+    }
+
+    impl ConsignmentApi for GenesisOnly {
+        type BundleIter<'container> = std::slice::Iter<'container, AnchoredBundle>;
+
+        fn schema(&self) -> &SubSchema { &self.schema }
+
+        fn operation(&self, opid: OpId) -> Option<OpRef<'_>> {
+            (opid == self.genesis.id()).then_some(OpRef::Genesis(&self.genesis))
+        }
+
+        fn genesis(&self) -> &Genesis { &self.genesis }
+
+        fn transition(&self, _opid: OpId) -> Option<&Transition> { None }
+
+        fn extension(&self, _opid: OpId) -> Option<&Extension> { None }
+
+        fn terminals(&self) -> BTreeSet<(BundleId, SecretSeal)> { none!() }
+
+        fn anchored_bundles(&self) -> Self::BundleIter<'_> { [].iter() }
+
+        fn bundle_by_id(&self, _bundle_id: BundleId) -> Option<&TransitionBundle> { None }
+
+        fn op_ids_except(&self, ids: &BTreeSet<OpId>) -> BTreeSet<OpId> {
+            bset![self.genesis.id()].difference(ids).copied().collect()
+        }
+
+        fn has_operation(&self, opid: OpId) -> bool { opid == self.genesis.id() }
+
+        fn known_transitions_by_bundle_id(&self, _bundle_id: BundleId) -> Option<Vec<&Transition>> {
+            None
+        }
+    }
+
+    struct NoTx;
+
+    impl ResolveTx for NoTx {
+        fn resolve_tx(&self, txid: Txid) -> Result<Tx, TxResolverError> {
+            Err(TxResolverError::Unknown(txid))
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+    #[strict_type(lib = "Test")]
+    struct Meta(u8);
+
+    fn issue(state: ConcealedValue) -> GenesisOnly {
+        let lib = LibBuilder::new(libname!("Test"), None::<Dependency>)
+            .transpile::<Meta>()
+            .compile()
+            .unwrap();
+        let sys = SystemBuilder::new()
+            .import(lib)
+            .unwrap()
+            .finalize()
+            .unwrap();
+        let mut schema = SubSchema {
+            owned_types: tiny_bmap! { TY => StateSchema::fungible(FungibleType::Unsigned64Bit) },
+            ..default!()
+        };
+        schema.genesis.metadata = *sys.resolve("Test.Meta").unwrap();
+        schema.genesis.assignments = tiny_bmap! { TY => Occurrences::Once };
+        schema.type_system = sys.into_type_system();
+        let assign = AssignFungible::<GenesisSeal>::ConfidentialState {
+            seal: GenesisSeal::strict_dumb(),
+            state,
+        };
+        let mut genesis = Genesis::strict_dumb();
+        genesis.schema_id = schema.schema_id();
+        genesis.metadata = SmallBlob::try_from(vec![1]).unwrap();
+        genesis.assignments = Assignments::from_inner(tiny_bmap! {
+            TY => TypedAssigns::Fungible(SmallVec::try_from_iter([assign]).unwrap())
+        });
+        GenesisOnly { schema, genesis }
+    }
+
+    #[test]
+    fn public_amount() {
+        let consignment = issue(ConcealedValue::public(100));
+        let status = Validator::validate(&consignment, &NoTx);
+        assert_eq!(status.failures, vec![]);
+
+        // public amount not matching the commitment
+        let forged = ConcealedValue::from_parts(
+            PedersenCommitment::commit_public(100),
+            ConcealedValue::public(1000).range_proof,
+        );
+        let consignment = issue(forged);
+        let opid = consignment.genesis.id();
+        let status = Validator::validate(&consignment, &NoTx);
+        assert_eq!(status.failures, vec![Failure::BulletproofsInvalid(
+            opid,
+            TY,
+            RangeProofError::PublicValueMismatch(1000)
+        )]);
+
+        // zero placeholder with a commitment to the public zero amount is not
+        // a public amount
+        let placeholder = ConcealedValue::from_parts(
+            PedersenCommitment::commit_public(0),
+            RangeProof::Placeholder(NoiseDumb::strict_dumb()),
+        );
+        let consignment = issue(placeholder);
+        let opid = consignment.genesis.id();
+        let status = Validator::validate(&consignment, &NoTx);
+        assert_eq!(status.failures, vec![Failure::BulletproofsInvalid(
+            opid,
+            TY,
+            RangeProofError::BulletproofsAbsent
+        )]);
+    }
+}
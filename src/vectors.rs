@@ -0,0 +1,228 @@
+// RGB Core Library: consensus layer for RGB smart contracts.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2023 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2023 LNP/BP Standards Association. All rights reserved.
+// Copyright (C) 2019-2023 Dr Maxim Orlovsky. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict encoding test vectors for RGB consensus data types, allowing other
+//! implementations to check conformance of their encoders with this library.
+//!
+//! Each vector pairs a value with its expected strict-encoded bytes, and is
+//! bound to the strict type library name [`LIB_NAME_RGB`] of the current
+//! version of the consensus layer. Any change to the expected bytes is a
+//! consensus-breaking change.
+
+use aluvm::library::{Lib, LibSeg, LibSite};
+use amplify::confinement::Confined;
+use amplify::hex::FromHex;
+use amplify::RawArray;
+use commit_verify::CommitVerify;
+use strict_encoding::{StrictDecode, StrictDumb, StrictEncode, StrictReader, StrictWriter};
+
+use crate::vm::{AluScript, EntryPoint};
+use crate::{
+    AssignmentType, AttachId, BlindingFactor, ConcealedValue, ContractId, MediaType, NoiseDumb,
+    PedersenCommitment, RangeProof, RevealedAttach, RevealedValue, Salt, LIB_NAME_RGB,
+};
+
+/// Strict encoding test vector.
+#[derive(Clone, Debug)]
+pub struct TestVector<T> {
+    /// Strict type library the vector belongs to.
+    pub lib: &'static str,
+    /// Human-readable name of the vector.
+    pub name: &'static str,
+    /// Value to be encoded.
+    pub value: T,
+    /// Expected result of the strict encoding of the value.
+    pub expected: Vec<u8>,
+}
+
+impl<T: StrictEncode> TestVector<T> {
+    fn with(name: &'static str, value: T, expected_hex: &str) -> Self {
+        TestVector {
+            lib: LIB_NAME_RGB,
+            name,
+            value,
+            expected: Vec::<u8>::from_hex(expected_hex).expect("hardcoded test vector hex"),
+        }
+    }
+
+    /// Returns strict encoding of the vector value.
+    pub fn encode(&self) -> Vec<u8> {
+        self.value
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .expect("in-memory encoding")
+            .unbox()
+    }
+
+    /// Checks that the strict encoding of the vector value matches the
+    /// expected bytes.
+    pub fn check(&self) -> bool { self.encode() == self.expected }
+}
+
+fn blinding(byte: u8) -> BlindingFactor {
+    BlindingFactor::try_from([byte; 32]).expect("hardcoded blinding factor")
+}
+
+fn contract(byte: u8) -> ContractId { ContractId::from([byte; 32]) }
+
+fn noise(byte: u8) -> NoiseDumb {
+    NoiseDumb::strict_decode(&mut StrictReader::in_memory(vec![byte; 512], 512))
+        .expect("hardcoded noise data")
+}
+
+/// Test vectors for [`RevealedValue`].
+pub fn revealed_value() -> Vec<TestVector<RevealedValue>> {
+    vec![
+        TestVector::with(
+            "dumb",
+            RevealedValue::strict_dumb(),
+            "080000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ),
+        TestVector::with(
+            "100000",
+            RevealedValue::with(100_000u64, blinding(0x11)),
+            "08a086010000000000111111111111111111111111111111111111111111111111111111111111111100",
+        ),
+        TestVector::with(
+            "max",
+            RevealedValue::with(u64::MAX, blinding(0x7F)),
+            "08ffffffffffffffff7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f00",
+        ),
+        TestVector::with(
+            "100000 contract",
+            RevealedValue::with(100_000u64, blinding(0x11)).bind_to_contract(contract(0x22)),
+            &format!(
+                "{}01{}",
+                "08a0860100000000001111111111111111111111111111111111111111111111111111111111111111",
+                "f94c487cf56a09f0446770a157fd2549518c36d9c6169fad7c97cfd22ee0ba4d"
+            ),
+        ),
+    ]
+}
+
+/// Test vectors for [`ConcealedValue`] with a placeholder range proof.
+pub fn concealed_value() -> Vec<TestVector<ConcealedValue>> {
+    let revealed = RevealedValue::with(100_000u64, blinding(0x11));
+    vec![
+        TestVector::with(
+            "100000",
+            ConcealedValue {
+                commitment: PedersenCommitment::commit(&revealed),
+                range_proof: RangeProof::Placeholder(noise(0xA5)),
+            },
+            &format!(
+                "{}ff{}",
+                "08b9cdec8a2788774a5a85b616260f5ea509ffb40c2215cb1f20b82a069ec91a69",
+                "a5".repeat(512)
+            ),
+        ),
+        TestVector::with(
+            "100000 contract",
+            ConcealedValue {
+                commitment: PedersenCommitment::commit(&revealed.bind_to_contract(contract(0x22))),
+                range_proof: RangeProof::Placeholder(noise(0xA5)),
+            },
+            &format!(
+                "{}ff{}",
+                "082034cc1edf09691237cf7feb0aa0af6f974ee9fd37ead14f6f1cacbdd01d5203",
+                "a5".repeat(512)
+            ),
+        ),
+    ]
+}
+
+/// Test vectors for [`RevealedAttach`].
+pub fn revealed_attach() -> Vec<TestVector<RevealedAttach>> {
+    vec![
+        TestVector::with(
+            "dumb",
+            RevealedAttach::strict_dumb(),
+            "0000000000000000000000000000000000000000000000000000000000000000ff0000000000000000",
+        ),
+        TestVector::with(
+            "any",
+            RevealedAttach::with_salt(
+                AttachId::from_raw_array([0x22; 32]),
+                MediaType::Any,
+                Salt::from(0x0123_4567_89AB_CDEF),
+            ),
+            "2222222222222222222222222222222222222222222222222222222222222222ffefcdab8967452301",
+        ),
+    ]
+}
+
+/// Test vectors for [`AluScript`].
+pub fn alu_script() -> Vec<TestVector<AluScript>> {
+    let lib = Lib::with("ALU", vec![0x00; 4], vec![], LibSeg::default()).expect("hardcoded lib");
+    let script = AluScript {
+        libs: Confined::try_from_iter([(lib.id(), lib.clone())]).expect("single lib"),
+        entry_points: Confined::try_from_iter([
+            (EntryPoint::ValidateGenesis, LibSite::with(0, lib.id())),
+            (EntryPoint::ValidateOwnedState(AssignmentType::with(1)), LibSite::with(2, lib.id())),
+        ])
+        .expect("two entry points"),
+    };
+    vec![
+        TestVector::with("empty", AluScript::default(), "000000"),
+        TestVector::with(
+            "single lib",
+            script,
+            "01\
+             17879f4e07c999b438da74d6135b8acfc5d01d0b41dd6a996e936334865504a9\
+             0d00\
+             03414c55040000000000000000\
+             0200\
+             000000\
+             17879f4e07c999b438da74d6135b8acfc5d01d0b41dd6a996e936334865504a9\
+             0000\
+             040100\
+             17879f4e07c999b438da74d6135b8acfc5d01d0b41dd6a996e936334865504a9\
+             0200",
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::ToHex;
+
+    use super::*;
+
+    fn check<T: StrictEncode>(vectors: Vec<TestVector<T>>) {
+        for vector in vectors {
+            assert_eq!(vector.lib, LIB_NAME_RGB);
+            assert_eq!(
+                vector.encode().to_hex(),
+                vector.expected.to_hex(),
+                "vector {}",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn vectors() {
+        check(revealed_value());
+        check(concealed_value());
+        check(revealed_attach());
+        check(alu_script());
+    }
+}
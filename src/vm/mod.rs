@@ -35,4 +35,8 @@ pub use isa::RgbIsa;
 pub use op_contract::ContractOp;
 pub use op_timechain::TimechainOp;
 pub use runtime::AluRuntime;
-pub use script::{AluScript, EntryPoint, LIBS_MAX_TOTAL};
+pub use script::{
+    AluScript, AluScriptBuildError, AluScriptBuilder, AluScriptDiff, ComplexityEstimate,
+    CoverageFlags, EntryPoint, EntryPointKind, EntryPointParseError, ReserializeError,
+    ALU_SCRIPT_FINGERPRINT_TAG, LIBS_MAX_TOTAL,
+};
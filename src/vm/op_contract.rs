@@ -34,6 +34,7 @@ use amplify::Wrapper;
 use strict_encoding::StrictSerialize;
 
 use super::opcodes::*;
+use crate::schema::{AssignmentType, GlobalStateType};
 use crate::validation::OpInfo;
 use crate::{Assign, TypedAssigns};
 
@@ -42,22 +43,22 @@ pub enum ContractOp {
     /// Counts number of inputs (previous state entries) of the provided type
     /// and assigns the number to the destination `a16` register.
     #[display("cnp      {0},a16{1}")]
-    CnP(u16, Reg16),
+    CnP(AssignmentType, Reg16),
 
     /// Counts number of outputs (owned state entries) of the provided type
     /// and assigns the number to the destination `a16` register.
     #[display("cns      {0},a16{1}")]
-    CnS(u16, Reg16),
+    CnS(AssignmentType, Reg16),
 
     /// Counts number of inputs (previous state entries) of the provided type
     /// and assigns the number to the destination `a8` register.
     #[display("cng      {0},a8{1}")]
-    CnG(u16, Reg16),
+    CnG(GlobalStateType, Reg16),
 
     /// Counts number of inputs (previous state entries) of the provided type
     /// and assigns the number to the destination `a16` register.
     #[display("cnc      {0},a16{1}")]
-    CnC(u16, Reg16),
+    CnC(GlobalStateType, Reg16),
 
     /// Loads input (previous) state with type id from the first argument and
     /// index from the second argument into a register provided in the third
@@ -68,7 +69,7 @@ pub enum ContractOp {
     ///
     /// If the state at the index is concealed, sets destination to `None`.
     #[display("ldp      {0},{1},{2}")]
-    LdP(u16, u16, RegS),
+    LdP(AssignmentType, u16, RegS),
 
     /// Loads owned structured state with type id from the first argument and
     /// index from the second argument into a register provided in the third
@@ -79,7 +80,7 @@ pub enum ContractOp {
     ///
     /// If the state at the index is concealed, sets destination to `None`.
     #[display("lds      {0},{1},{2}")]
-    LdS(u16, u16, RegS),
+    LdS(AssignmentType, u16, RegS),
 
     /// Loads owned fungible state with type id from the first argument and
     /// index from the second argument into `a64` register provided in the third
@@ -90,7 +91,7 @@ pub enum ContractOp {
     ///
     /// If the state at the index is concealed, sets destination to `None`.
     #[display("ldf      {0},{1},a64{2}")]
-    LdF(u16, u16, Reg16),
+    LdF(AssignmentType, u16, Reg16),
 
     /// Loads global state from the current operation with type id from the
     /// first argument and index from the second argument into a register
@@ -98,7 +99,7 @@ pub enum ContractOp {
     ///
     /// If the state is absent sets `st0` to `false` and terminates the program.
     #[display("ldg      {0},{1},{2}")]
-    LdG(u16, u8, RegS),
+    LdG(GlobalStateType, u8, RegS),
 
     /// Loads part of the contract global state with type id from the first
     /// argument at the depth from the second argument into a register
@@ -107,7 +108,7 @@ pub enum ContractOp {
     /// If the state is absent or concealed sets destination to `None`.
     /// Does not modify content of `st0` register.
     #[display("ldc      {0},{1},{2}")]
-    LdC(u16, u16, RegS),
+    LdC(GlobalStateType, u16, RegS),
 
     /// Loads operation metadata into a register provided in the third argument.
     ///
@@ -126,7 +127,7 @@ pub enum ContractOp {
     /// If verification succeeds, doesn't changes `st0` value; otherwise sets it
     /// to `false`.
     #[display("pcvs     {0}")]
-    PcVs(u16),
+    PcVs(AssignmentType),
     /*
     /// Verifies corrected sum of pedersen commitments adding a value taken from `RegR` to the list
     /// of inputs (negatives).
@@ -314,51 +315,51 @@ impl Bytecode for ContractOp {
     where W: Write {
         match self {
             ContractOp::CnP(state_type, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::CnS(state_type, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::CnG(state_type, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::CnC(state_type, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::LdP(state_type, index, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u16(*index)?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::LdS(state_type, index, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u16(*index)?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::LdF(state_type, index, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u16(*index)?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::LdG(state_type, index, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u8(*index)?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
             }
             ContractOp::LdC(state_type, index, reg) => {
-                writer.write_u16(*state_type)?;
+                writer.write_u16(state_type.into_inner())?;
                 writer.write_u16(*index)?;
                 writer.write_u4(reg)?;
                 writer.write_u4(u4::ZERO)?;
@@ -368,7 +369,7 @@ impl Bytecode for ContractOp {
                 writer.write_u4(u4::ZERO)?;
             }
 
-            ContractOp::PcVs(state_type) => writer.write_u16(*state_type)?,
+            ContractOp::PcVs(state_type) => writer.write_u16(state_type.into_inner())?,
 
             ContractOp::Fail(_) => {}
         }
@@ -382,48 +383,68 @@ impl Bytecode for ContractOp {
     {
         Ok(match reader.read_u8()? {
             INSTR_CNP => {
-                let i = Self::CnP(reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::CnP(reader.read_u16()?.into(), reader.read_u4()?.into());
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
             INSTR_CNS => {
-                let i = Self::CnS(reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::CnS(reader.read_u16()?.into(), reader.read_u4()?.into());
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
             INSTR_CNG => {
-                let i = Self::CnG(reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::CnG(reader.read_u16()?.into(), reader.read_u4()?.into());
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
             INSTR_CNC => {
-                let i = Self::CnC(reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::CnC(reader.read_u16()?.into(), reader.read_u4()?.into());
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
 
             INSTR_LDP => {
-                let i = Self::LdP(reader.read_u16()?, reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::LdP(
+                    reader.read_u16()?.into(),
+                    reader.read_u16()?,
+                    reader.read_u4()?.into(),
+                );
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
             INSTR_LDS => {
-                let i = Self::LdS(reader.read_u16()?, reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::LdS(
+                    reader.read_u16()?.into(),
+                    reader.read_u16()?,
+                    reader.read_u4()?.into(),
+                );
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
             INSTR_LDF => {
-                let i = Self::LdF(reader.read_u16()?, reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::LdF(
+                    reader.read_u16()?.into(),
+                    reader.read_u16()?,
+                    reader.read_u4()?.into(),
+                );
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
             INSTR_LDG => {
-                let i = Self::LdG(reader.read_u16()?, reader.read_u8()?, reader.read_u4()?.into());
+                let i = Self::LdG(
+                    reader.read_u16()?.into(),
+                    reader.read_u8()?,
+                    reader.read_u4()?.into(),
+                );
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
             INSTR_LDC => {
-                let i = Self::LdC(reader.read_u16()?, reader.read_u16()?, reader.read_u4()?.into());
+                let i = Self::LdC(
+                    reader.read_u16()?.into(),
+                    reader.read_u16()?,
+                    reader.read_u4()?.into(),
+                );
                 reader.read_u4()?; // Discard garbage bits
                 i
             }
@@ -433,7 +454,7 @@ impl Bytecode for ContractOp {
                 i
             }
 
-            INSTR_PCVS => Self::PcVs(reader.read_u16()?),
+            INSTR_PCVS => Self::PcVs(reader.read_u16()?.into()),
 
             x => Self::Fail(x),
         })
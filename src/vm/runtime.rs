@@ -47,15 +47,15 @@ impl<'script> AluRuntime<'script> {
                 self.run(EntryPoint::ValidateGenesis, &regs, info)?;
             }
             OpFullType::StateTransition(ty) => {
-                self.run(EntryPoint::ValidateTransition(ty), &regs, info)?;
+                self.run(ty.into(), &regs, info)?;
             }
             OpFullType::StateExtension(ty) => {
-                self.run(EntryPoint::ValidateExtension(ty), &regs, info)?;
+                self.run(ty.into(), &regs, info)?;
             }
         }
 
         for ty in info.global.keys() {
-            self.run(EntryPoint::ValidateGlobalState(*ty), &regs, info)?;
+            self.run((*ty).into(), &regs, info)?;
         }
 
         let used_state = info
@@ -66,7 +66,7 @@ impl<'script> AluRuntime<'script> {
             .copied()
             .collect::<BTreeSet<_>>();
         for ty in used_state {
-            self.run(EntryPoint::ValidateGlobalState(ty), &regs, info)?;
+            self.run(ty.into(), &regs, info)?;
         }
 
         Ok(())
@@ -106,3 +106,53 @@ struct RegSetup {
     pub nums: BTreeMap<(RegAFR, Reg32), Number>,
     pub data: BTreeMap<RegS, Vec<u8>>,
 }
+
+#[cfg(test)]
+mod test {
+    use aluvm::library::{Lib, LibSite};
+    use aluvm::reg::Reg16;
+    use amplify::confinement::Confined;
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::vm::{ContractOp, RgbIsa};
+    use crate::{
+        AssignmentType, Assignments, GlobalStateType, GraphSeal, OpRef, Operation, Transition,
+        TypedAssigns, Valencies,
+    };
+
+    const TY: u16 = 2;
+
+    // Script with a single library called at the provided entry point, which
+    // always fails since it accesses contract global state, which is not
+    // supported yet.
+    fn failing_script(entry: EntryPoint) -> AluScript {
+        let instr = ContractOp::CnC(GlobalStateType::with(TY), Reg16::Reg0);
+        let lib = Lib::assemble(&[RgbIsa::Contract(instr)]).unwrap();
+        AluScript {
+            entry_points: Confined::try_from_iter([(entry, LibSite::with(0, lib.id()))]).unwrap(),
+            libs: Confined::try_from_iter([(lib.id(), lib)]).unwrap(),
+        }
+    }
+
+    #[test]
+    fn owned_state_entry_point() {
+        let mut transition = Transition::strict_dumb();
+        transition
+            .assignments
+            .insert(AssignmentType::with(TY), TypedAssigns::Declarative(none!()))
+            .unwrap();
+        let op = OpRef::Transition(&transition);
+        let prev_state = Assignments::<GraphSeal>::default();
+        let redeemed = Valencies::default();
+        let info = OpInfo::with(op.id(), false, &op, &prev_state, &redeemed);
+
+        let owned = failing_script(EntryPoint::ValidateOwnedState(AssignmentType::with(TY)));
+        assert!(AluRuntime::new(&owned).run_validations(&info).is_err());
+
+        // Global state validation must not be run for the owned state of the
+        // same numeric type
+        let global = failing_script(EntryPoint::ValidateGlobalState(GlobalStateType::with(TY)));
+        assert_eq!(AluRuntime::new(&global).run_validations(&info), Ok(()));
+    }
+}
@@ -20,16 +20,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{btree_map, BTreeMap};
+use std::collections::{btree_map, BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
 use std::io;
+use std::num::ParseIntError;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+use std::str::FromStr;
 
 use aluvm::data::encoding::{Decode, Encode};
-use aluvm::library::{Lib, LibId, LibSite};
+use aluvm::isa::{Bytecode, ControlFlowOp, Instr, InstructionSet};
+use aluvm::library::{Cursor, Lib, LibId, LibSite, Read};
 use aluvm::Program;
 use amplify::confinement::{Confined, SmallBlob, SmallOrdMap, TinyOrdMap};
+use amplify::Wrapper;
+use commit_verify::{DigestExt, Sha256};
 use strict_encoding::{
     DecodeError, ReadStruct, StrictDecode, StrictEncode, StrictProduct, StrictStruct, StrictTuple,
-    StrictType, TypedRead, TypedWrite, WriteStruct,
+    StrictType, StrictWriter, TypedRead, TypedWrite, WriteStruct,
 };
 
 use crate::vm::RgbIsa;
@@ -39,7 +46,56 @@ use crate::{AssignmentType, ExtensionType, GlobalStateType, TransitionType, LIB_
 /// i.e. maximal number of nodes in a library dependency tree.
 pub const LIBS_MAX_TOTAL: usize = 1024;
 
-#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+/// Tag used by [`AluScript::fingerprint`].
+pub const ALU_SCRIPT_FINGERPRINT_TAG: [u8; 32] = *b"urn:lnpbp:rgb:alu-script:v1#2310";
+
+/// Entry point of a validation script, selecting the operation or state type
+/// the script validates.
+///
+/// Each variant takes its own distinct type id, so they can't be mixed up:
+///
+/// ```
+/// # use rgb::vm::EntryPoint;
+/// # use rgb::{AssignmentType, TransitionType};
+/// let entry = EntryPoint::ValidateTransition(TransitionType::with(1));
+/// assert_eq!(entry.to_string(), "transition:1");
+/// let entry = EntryPoint::ValidateOwnedState(AssignmentType::with(4));
+/// assert_eq!(entry.to_string(), "owned:4");
+/// ```
+///
+/// Entry points for operation and state types can also be obtained by
+/// converting the type id, which picks the matching variant:
+///
+/// ```
+/// # use rgb::vm::EntryPoint;
+/// # use rgb::{AssignmentType, ExtensionType, GlobalStateType, TransitionType};
+/// assert_eq!(
+///     EntryPoint::from(TransitionType::with(1)),
+///     EntryPoint::ValidateTransition(TransitionType::with(1))
+/// );
+/// assert_eq!(
+///     EntryPoint::from(ExtensionType::with(2)),
+///     EntryPoint::ValidateExtension(ExtensionType::with(2))
+/// );
+/// assert_eq!(
+///     EntryPoint::from(GlobalStateType::with(3)),
+///     EntryPoint::ValidateGlobalState(GlobalStateType::with(3))
+/// );
+/// let entry: EntryPoint = AssignmentType::with(4).into();
+/// assert_eq!(entry, EntryPoint::ValidateOwnedState(AssignmentType::with(4)));
+/// ```
+///
+/// ```compile_fail
+/// # use rgb::vm::EntryPoint;
+/// # use rgb::GlobalStateType;
+/// let entry = EntryPoint::ValidateTransition(GlobalStateType::with(1));
+/// ```
+///
+/// ```compile_fail
+/// # use rgb::vm::EntryPoint;
+/// let entry = EntryPoint::ValidateOwnedState(4u16);
+/// ```
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display, From)]
 #[derive(StrictDumb)]
 #[strict_type(lib = LIB_NAME_RGB)]
 #[cfg_attr(
@@ -49,13 +105,159 @@ pub const LIBS_MAX_TOTAL: usize = 1024;
 )]
 pub enum EntryPoint {
     #[strict_type(dumb)]
+    #[display("genesis")]
     ValidateGenesis,
+    #[display("transition:{0}")]
+    #[from]
     ValidateTransition(TransitionType),
+    #[display("extension:{0}")]
+    #[from]
     ValidateExtension(ExtensionType),
+    #[display("global:{0}")]
+    #[from]
     ValidateGlobalState(GlobalStateType),
+    #[display("owned:{0}")]
+    #[from]
     ValidateOwnedState(AssignmentType),
 }
 
+/// Kind of an [`EntryPoint`], without the operation or state type.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", rename_all = "camelCase")
+)]
+#[repr(u8)]
+pub enum EntryPointKind {
+    Genesis = 0,
+    Transition = 1,
+    Extension = 2,
+    Global = 3,
+    Owned = 4,
+}
+
+impl EntryPoint {
+    /// Returns kind of the entry point.
+    pub fn kind(&self) -> EntryPointKind {
+        match self {
+            EntryPoint::ValidateGenesis => EntryPointKind::Genesis,
+            EntryPoint::ValidateTransition(_) => EntryPointKind::Transition,
+            EntryPoint::ValidateExtension(_) => EntryPointKind::Extension,
+            EntryPoint::ValidateGlobalState(_) => EntryPointKind::Global,
+            EntryPoint::ValidateOwnedState(_) => EntryPointKind::Owned,
+        }
+    }
+
+    /// Returns operation or state type of the entry point, or `None` for the
+    /// genesis entry point, which has no subtypes.
+    pub fn subtype(&self) -> Option<u16> {
+        match self {
+            EntryPoint::ValidateGenesis => None,
+            EntryPoint::ValidateTransition(ty) => Some(ty.into_inner()),
+            EntryPoint::ValidateExtension(ty) => Some(ty.into_inner()),
+            EntryPoint::ValidateGlobalState(ty) => Some(ty.into_inner()),
+            EntryPoint::ValidateOwnedState(ty) => Some(ty.into_inner()),
+        }
+    }
+}
+
+/// Set of [`EntryPointKind`]s covered by the entry points of a script, see
+/// [`AluScript::coverage_flags`].
+///
+/// Each kind is represented by a bit with the position equal to the kind
+/// discriminant.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct CoverageFlags(u8);
+
+impl CoverageFlags {
+    pub const GENESIS: Self = Self::kind(EntryPointKind::Genesis);
+    pub const TRANSITION: Self = Self::kind(EntryPointKind::Transition);
+    pub const EXTENSION: Self = Self::kind(EntryPointKind::Extension);
+    pub const GLOBAL: Self = Self::kind(EntryPointKind::Global);
+    pub const OWNED: Self = Self::kind(EntryPointKind::Owned);
+
+    /// Returns flags for a single entry point `kind`.
+    pub const fn kind(kind: EntryPointKind) -> Self { CoverageFlags(1 << kind as u8) }
+
+    /// Returns flags with no entry point kinds.
+    pub const fn empty() -> Self { CoverageFlags(0) }
+
+    /// Returns flags with all entry point kinds.
+    pub const fn all() -> Self {
+        CoverageFlags(
+            Self::GENESIS.0 |
+                Self::TRANSITION.0 |
+                Self::EXTENSION.0 |
+                Self::GLOBAL.0 |
+                Self::OWNED.0,
+        )
+    }
+
+    /// Returns the raw bitmask.
+    pub const fn bits(self) -> u8 { self.0 }
+
+    /// Detects whether no entry point kinds are covered.
+    pub const fn is_empty(self) -> bool { self.0 == 0 }
+
+    /// Detects whether all the kinds from `other` are covered.
+    pub const fn contains(self, other: CoverageFlags) -> bool { self.0 & other.0 == other.0 }
+}
+
+impl BitOr for CoverageFlags {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output { CoverageFlags(self.0 | rhs.0) }
+}
+
+impl BitOrAssign for CoverageFlags {
+    fn bitor_assign(&mut self, rhs: Self) { self.0 |= rhs.0 }
+}
+
+impl BitAnd for CoverageFlags {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output { CoverageFlags(self.0 & rhs.0) }
+}
+
+/// Errors parsing [`EntryPoint`] from a string.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum EntryPointParseError {
+    /// unknown entry point kind '{0}'.
+    UnknownKind(String),
+
+    /// entry point '{0}' requires operation or state type to be specified.
+    NoSubtype(String),
+
+    /// invalid entry point subtype; {0}.
+    #[from]
+    InvalidSubtype(ParseIntError),
+}
+
+impl FromStr for EntryPoint {
+    type Err = EntryPointParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((kind, ty)) = s.split_once(':') else {
+            return match s {
+                "genesis" => Ok(EntryPoint::ValidateGenesis),
+                "transition" | "extension" | "global" | "owned" => {
+                    Err(EntryPointParseError::NoSubtype(s.to_owned()))
+                }
+                _ => Err(EntryPointParseError::UnknownKind(s.to_owned())),
+            };
+        };
+        let ty = ty.parse::<u16>()?;
+        Ok(match kind {
+            "transition" => EntryPoint::ValidateTransition(ty.into()),
+            "extension" => EntryPoint::ValidateExtension(ty.into()),
+            "global" => EntryPoint::ValidateGlobalState(ty.into()),
+            "owned" => EntryPoint::ValidateOwnedState(ty.into()),
+            _ => return Err(EntryPointParseError::UnknownKind(kind.to_owned())),
+        })
+    }
+}
+
 impl StrictType for EntryPoint {
     const STRICT_LIB_NAME: &'static str = LIB_NAME_RGB;
 }
@@ -66,32 +268,59 @@ impl StrictTuple for EntryPoint {
 impl StrictEncode for EntryPoint {
     fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
         let mut val = [0u8; 3];
-        let (ty, subty) = match self {
-            EntryPoint::ValidateGenesis => (0, 0u16),
-            EntryPoint::ValidateTransition(ty) => (1, *ty),
-            EntryPoint::ValidateExtension(ty) => (2, *ty),
-            EntryPoint::ValidateGlobalState(ty) => (3, *ty),
-            EntryPoint::ValidateOwnedState(ty) => (4, *ty),
-        };
-        val[0] = ty;
-        val[1..].copy_from_slice(&subty.to_le_bytes());
+        val[0] = self.kind() as u8;
+        val[1..].copy_from_slice(&self.subtype().unwrap_or_default().to_le_bytes());
         val.strict_encode(writer)
     }
 }
 impl StrictDecode for EntryPoint {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        EntryPoint::strict_decode_bounded(reader, u16::MAX)
+    }
+}
+
+impl EntryPoint {
+    /// Decodes entry point, additionally requiring its subtype not to exceed
+    /// `max_subtype`, for instance when a schema version restricts the range
+    /// of valid operation and state types.
+    ///
+    /// Entry points are encoded as three bytes: the [`EntryPointKind`] tag
+    /// followed by the little-endian subtype. The genesis entry point has no
+    /// subtype, which must be encoded as zero; any other value is rejected to
+    /// keep the encoding canonical.
+    ///
+    /// # Errors
+    ///
+    /// - [`DecodeError::EnumTagNotKnown`] for an unknown entry point kind tag;
+    /// - [`DecodeError::DataIntegrityError`] for a non-zero genesis subtype or
+    ///   a subtype exceeding `max_subtype`.
+    pub fn strict_decode_bounded(
+        reader: &mut impl TypedRead,
+        max_subtype: u16,
+    ) -> Result<Self, DecodeError> {
         let val = <[u8; 3]>::strict_decode(reader)?;
         let mut ty = [0u8; 2];
         ty.copy_from_slice(&val[1..]);
         let ty = u16::from_le_bytes(ty);
-        Ok(match val[0] {
-            0 => EntryPoint::ValidateGenesis,
-            1 => EntryPoint::ValidateTransition(ty),
-            2 => EntryPoint::ValidateExtension(ty),
-            3 => EntryPoint::ValidateGlobalState(ty),
-            4 => EntryPoint::ValidateOwnedState(ty),
+        let entry = match val[0] {
+            0 if ty != 0 => {
+                return Err(DecodeError::DataIntegrityError(format!(
+                    "genesis entry point must have zero subtype, while {ty} is found"
+                )));
+            }
+            0 => return Ok(EntryPoint::ValidateGenesis),
+            1 => EntryPoint::ValidateTransition(ty.into()),
+            2 => EntryPoint::ValidateExtension(ty.into()),
+            3 => EntryPoint::ValidateGlobalState(ty.into()),
+            4 => EntryPoint::ValidateOwnedState(ty.into()),
             x => return Err(DecodeError::EnumTagNotKnown(s!("EntryPoint"), x)),
-        })
+        };
+        if ty > max_subtype {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "entry point {entry} exceeds maximal subtype {max_subtype}"
+            )));
+        }
+        Ok(entry)
     }
 }
 
@@ -101,7 +330,7 @@ impl StrictDecode for EntryPoint {
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
-    serde(crate = "serde_crate", rename_all = "camelCase")
+    serde(crate = "serde_crate", into = "AluScriptSerde", try_from = "AluScriptSerde")
 )]
 pub struct AluScript {
     /// Libraries known to the runtime, identified by their hashes.
@@ -141,6 +370,11 @@ impl StrictEncode for AluScript {
         })
     }
 }
+/// Strict decoding does not check that the libraries are stored under their
+/// own ids: it is the consensus wire format, and rejecting such scripts
+/// would change which existing consignments can be decoded. The serde
+/// representation, which is not a consensus format and may be edited by
+/// hand, rejects such mismatches.
 impl StrictDecode for AluScript {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
         reader.read_struct(|r| {
@@ -163,6 +397,480 @@ impl StrictDecode for AluScript {
     }
 }
 
+/// Serde representation of [`AluScript`], where libraries are stored as
+/// base64-encoded binary AluVM library data keyed by library ids, and entry
+/// points are keyed by their string representation.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate", rename_all = "camelCase")]
+struct AluScriptSerde {
+    libs: BTreeMap<String, String>,
+    entry_points: BTreeMap<String, LibSite>,
+}
+
+/// Errors reconstructing [`AluScript`] from its serde representation.
+#[cfg(feature = "serde")]
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+enum AluScriptSerdeError {
+    /// invalid library id '{0}'.
+    LibId(String),
+
+    /// invalid base64 encoding of library {0}.
+    Base64(LibId),
+
+    /// invalid data for library {0}: {1}.
+    Lib(LibId, String),
+
+    /// library {0} data doesn't match its id.
+    ///
+    /// Unlike the strict decoding, which must keep accepting all data valid
+    /// under the consensus rules, the serde representation is not a
+    /// consensus format and is often written by hand, so mismatching ids are
+    /// reported as a likely editing error.
+    LibIdMismatch(LibId),
+
+    /// invalid entry point; {0}
+    EntryPoint(EntryPointParseError),
+
+    /// script exceeds the maximum number of libraries or entry points.
+    TooManyItems,
+}
+
+#[cfg(feature = "serde")]
+impl From<AluScript> for AluScriptSerde {
+    fn from(script: AluScript) -> Self {
+        use baid58::ToBaid58;
+        use base64::Engine;
+
+        let libs = script
+            .libs
+            .iter()
+            .map(|(id, lib)| {
+                (
+                    id.to_baid58().to_string(),
+                    base64::engine::general_purpose::STANDARD.encode(lib.serialize()),
+                )
+            })
+            .collect();
+        let entry_points = script
+            .entry_points
+            .iter()
+            .map(|(entry, site)| (entry.to_string(), *site))
+            .collect();
+        AluScriptSerde { libs, entry_points }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AluScriptSerde> for AluScript {
+    type Error = AluScriptSerdeError;
+
+    fn try_from(data: AluScriptSerde) -> Result<Self, Self::Error> {
+        use base64::Engine;
+
+        let mut libs = BTreeMap::new();
+        for (id, lib) in data.libs {
+            let id = LibId::from_str(&id).map_err(|_| AluScriptSerdeError::LibId(id))?;
+            let lib = base64::engine::general_purpose::STANDARD
+                .decode(lib)
+                .map_err(|_| AluScriptSerdeError::Base64(id))?;
+            let lib = Lib::deserialize(lib)
+                .map_err(|err| AluScriptSerdeError::Lib(id, err.to_string()))?;
+            if lib.id() != id {
+                return Err(AluScriptSerdeError::LibIdMismatch(id));
+            }
+            libs.insert(id, lib);
+        }
+        let entry_points = data
+            .entry_points
+            .into_iter()
+            .map(|(entry, site)| EntryPoint::from_str(&entry).map(|entry| (entry, site)))
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map_err(AluScriptSerdeError::EntryPoint)?;
+        Ok(AluScript {
+            libs: Confined::try_from(libs).map_err(|_| AluScriptSerdeError::TooManyItems)?,
+            entry_points: Confined::try_from(entry_points)
+                .map_err(|_| AluScriptSerdeError::TooManyItems)?,
+        })
+    }
+}
+
+impl AluScript {
+    /// Detects whether the script has no libraries and no entry points, i.e.
+    /// the schema relies only on the built-in state validation.
+    pub fn is_empty(&self) -> bool { self.libs.is_empty() && self.entry_points.is_empty() }
+
+    /// Checks whether the script has validator code for the given entry point.
+    pub fn has_validator_for(&self, entry: &EntryPoint) -> bool {
+        self.entry_points.contains_key(entry)
+    }
+
+    /// Returns the set of entry point kinds for which the script has at least
+    /// one validator.
+    pub fn coverage_flags(&self) -> CoverageFlags {
+        self.entry_points
+            .keys()
+            .fold(CoverageFlags::empty(), |flags, entry| flags | CoverageFlags::kind(entry.kind()))
+    }
+
+    /// Lists entry points whose validators are located in the library with
+    /// the given `id`, in the entry point order.
+    pub fn entry_points_for_lib(&self, id: LibId) -> Vec<EntryPoint> {
+        self.entry_points
+            .iter()
+            .filter(|(_, site)| site.lib == id)
+            .map(|(entry, _)| *entry)
+            .collect()
+    }
+
+    /// Lists library sites which are used as validators by more than one entry
+    /// point, together with those entry points in the entry point order.
+    ///
+    /// Sharing a validator between entry points is allowed and sometimes
+    /// intentional; the method is purely diagnostic and helps to detect
+    /// copy-paste errors in schema scripts.
+    pub fn find_shared_sites(&self) -> Vec<(LibSite, Vec<EntryPoint>)> {
+        let mut sites = BTreeMap::<LibSite, Vec<EntryPoint>>::new();
+        for (entry, site) in &self.entry_points {
+            sites.entry(*site).or_default().push(*entry);
+        }
+        sites
+            .into_iter()
+            .filter(|(_, entries)| entries.len() > 1)
+            .collect()
+    }
+
+    /// Statically estimates complexity of the validator for the `entry` point.
+    ///
+    /// The estimate is a heuristic upper bound for a single pass: it covers
+    /// all instructions from the entry point to the end of the library code,
+    /// ignoring loops and code of the called routines and libraries. Returns
+    /// `None` if the script has no validator for the entry point or its
+    /// library is missing.
+    pub fn estimate_complexity(&self, entry: EntryPoint) -> Option<ComplexityEstimate> {
+        let site = self.entry_points.get(&entry)?;
+        let lib = self.libs.get(&site.lib)?;
+
+        let mut estimate = ComplexityEstimate {
+            instructions: 0,
+            complexity: 0,
+            max_stack_depth: Some(0),
+        };
+        let mut cursor = Cursor::with(lib.code_segment(), lib.data_segment(), lib.libs_segment());
+        if cursor.seek(site.pos).is_err() {
+            return Some(estimate);
+        }
+        while !cursor.is_eof() {
+            let pos = cursor.pos();
+            let Ok(instr) = Instr::<RgbIsa>::decode(&mut cursor) else {
+                break;
+            };
+            estimate.instructions += 1;
+            estimate.complexity = estimate.complexity.saturating_add(instr.complexity());
+            if let Instr::ControlFlow(
+                ControlFlowOp::Routine(_) | ControlFlowOp::Call(_) | ControlFlowOp::Exec(_),
+            ) = instr
+            {
+                estimate.max_stack_depth = None;
+            }
+            // unknown instructions are not consumed by the decoder; they fail
+            // the script anyway
+            if cursor.pos() == pos {
+                break;
+            }
+        }
+        Some(estimate)
+    }
+
+    /// Computes fingerprint of the script for deduplication purposes: tagged
+    /// SHA256 hash (using [`ALU_SCRIPT_FINGERPRINT_TAG`]) of the libraries
+    /// with their ids and of the entry points, each sorted by their keys.
+    ///
+    /// The fingerprint doesn't depend on the order in which the libraries and
+    /// entry points were added to the script.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let mut engine = Sha256::from_tag(ALU_SCRIPT_FINGERPRINT_TAG);
+        let writer = StrictWriter::with(u32::MAX as usize, &mut engine);
+        self.strict_encode(writer).expect("hashers do not error");
+        engine.finish()
+    }
+
+    /// Lists all libraries used by the script together with their serialized
+    /// length, sorted from the largest to the smallest. Libraries of the same
+    /// size are ordered by their [`LibId`].
+    pub fn libs_by_size(&self) -> Vec<(LibId, usize)> {
+        let mut libs = self
+            .libs
+            .iter()
+            .map(|(id, lib)| (*id, lib.serialize().len()))
+            .collect::<Vec<_>>();
+        libs.sort_by(|(id1, len1), (id2, len2)| len2.cmp(len1).then(id1.cmp(id2)));
+        libs
+    }
+
+    /// Removes libraries which are not reachable from any of the entry points,
+    /// returning the number of removed libraries.
+    ///
+    /// A library is reachable if it hosts an entry point validator or is
+    /// listed in the libs segment of a reachable library; the analysis is
+    /// conservative and keeps all libraries which may be called.
+    pub fn prune_unused_libs(&mut self) -> usize {
+        let mut reachable = BTreeSet::<LibId>::new();
+        let mut queue = self
+            .entry_points
+            .values()
+            .map(|site| site.lib)
+            .collect::<Vec<_>>();
+        while let Some(id) = queue.pop() {
+            let Some(lib) = self.libs.get(&id) else {
+                continue;
+            };
+            if !reachable.insert(id) {
+                continue;
+            }
+            queue.extend(lib.libs_segment().iter().copied());
+        }
+
+        let before = self.libs.len();
+        let libs = self
+            .libs
+            .iter()
+            .filter(|(id, _)| reachable.contains(*id))
+            .map(|(id, lib)| (*id, lib.clone()))
+            .collect::<BTreeMap<_, _>>();
+        self.libs = Confined::try_from(libs).expect("subset of the libraries");
+        before - self.libs.len()
+    }
+
+    /// Re-serializes all script libraries with the current AluVM version,
+    /// allowing to migrate stored schemas after AluVM upgrades.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReserializeError::Deserialize`] if a library can't be read back by
+    ///   the current AluVM version;
+    /// - [`ReserializeError::IdChanged`] if the re-serialized library gets a
+    ///   different id, which would invalidate the entry points referencing the
+    ///   library.
+    pub fn reserialize_libs(&self) -> Result<AluScript, ReserializeError> {
+        let libs = self
+            .libs
+            .iter()
+            .map(|(id, lib)| {
+                let lib = Lib::deserialize(lib.serialize())
+                    .map_err(|err| ReserializeError::Deserialize(*id, err.to_string()))?;
+                if lib.id() != *id {
+                    return Err(ReserializeError::IdChanged {
+                        expected: *id,
+                        found: lib.id(),
+                    });
+                }
+                Ok((*id, lib))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        Ok(AluScript {
+            libs: Confined::try_from(libs).expect("same number of libraries"),
+            entry_points: self.entry_points.clone(),
+        })
+    }
+
+    /// Computes changes from this script to the `other` script, for instance
+    /// for reviewing validators of an upgraded schema.
+    pub fn diff(&self, other: &AluScript) -> AluScriptDiff {
+        let libs_added = other
+            .libs
+            .keys()
+            .filter(|id| !self.libs.contains_key(*id))
+            .copied()
+            .collect();
+        let libs_removed = self
+            .libs
+            .keys()
+            .filter(|id| !other.libs.contains_key(*id))
+            .copied()
+            .collect();
+        let libs_changed = self
+            .libs
+            .keys()
+            .filter(|id| other.libs.contains_key(*id))
+            .filter(|id| self.entry_points_using(**id) != other.entry_points_using(**id))
+            .copied()
+            .collect();
+
+        let mut entry_points_changed = BTreeMap::new();
+        for (entry, site) in &self.entry_points {
+            match other.entry_points.get(entry) {
+                Some(new) if new == site => {}
+                new => {
+                    entry_points_changed.insert(*entry, (Some(*site), new.copied()));
+                }
+            }
+        }
+        for (entry, site) in &other.entry_points {
+            if !self.entry_points.contains_key(entry) {
+                entry_points_changed.insert(*entry, (None, Some(*site)));
+            }
+        }
+
+        AluScriptDiff {
+            libs_added,
+            libs_removed,
+            libs_changed,
+            entry_points_changed,
+        }
+    }
+
+    fn entry_points_using(&self, id: LibId) -> BTreeMap<EntryPoint, u16> {
+        self.entry_points
+            .iter()
+            .filter(|(_, site)| site.lib == id)
+            .map(|(entry, site)| (*entry, site.pos))
+            .collect()
+    }
+}
+
+/// Changes between two versions of [`AluScript`], computed by
+/// [`AluScript::diff`].
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct AluScriptDiff {
+    /// Libraries present only in the new version of the script.
+    pub libs_added: BTreeSet<LibId>,
+
+    /// Libraries present only in the old version of the script.
+    pub libs_removed: BTreeSet<LibId>,
+
+    /// Libraries present in both versions, but used by a different set of
+    /// entry points or at different offsets. Since libraries are identified
+    /// by the hash of their content, the library code itself can't change.
+    pub libs_changed: BTreeSet<LibId>,
+
+    /// Entry points which were added, removed or retargeted, with their old
+    /// and new library sites.
+    pub entry_points_changed: BTreeMap<EntryPoint, (Option<LibSite>, Option<LibSite>)>,
+}
+
+impl AluScriptDiff {
+    /// Detects whether both versions of the script are identical.
+    pub fn is_empty(&self) -> bool {
+        self.libs_added.is_empty() &&
+            self.libs_removed.is_empty() &&
+            self.libs_changed.is_empty() &&
+            self.entry_points_changed.is_empty()
+    }
+}
+
+impl Display for AluScriptDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no changes\n");
+        }
+        for id in &self.libs_added {
+            writeln!(f, "+ lib {id}")?;
+        }
+        for id in &self.libs_removed {
+            writeln!(f, "- lib {id}")?;
+        }
+        for id in &self.libs_changed {
+            writeln!(f, "~ lib {id}")?;
+        }
+        for (entry, sites) in &self.entry_points_changed {
+            match sites {
+                (None, Some(new)) => writeln!(f, "+ {entry} -> {new}")?,
+                (Some(old), None) => writeln!(f, "- {entry} -> {old}")?,
+                (Some(old), Some(new)) => writeln!(f, "~ {entry}: {old} -> {new}")?,
+                (None, None) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Static complexity estimate of a validator, produced by
+/// [`AluScript::estimate_complexity`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ComplexityEstimate {
+    /// Number of instructions from the entry point to the end of the library
+    /// code.
+    pub instructions: usize,
+    /// Sum of the complexities of the instructions, as defined by the ISA.
+    pub complexity: u64,
+    /// Maximal depth of nested calls, which is zero for code without calls
+    /// and unknown (`None`) if the code calls routines or other libraries.
+    pub max_stack_depth: Option<usize>,
+}
+
+/// Errors re-serializing [`AluScript`] libraries with
+/// [`AluScript::reserialize_libs`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ReserializeError {
+    /// library {0} can't be deserialized by the current AluVM version; {1}.
+    Deserialize(LibId, String),
+
+    /// library {expected} changes its id to {found} after re-serialization.
+    IdChanged { expected: LibId, found: LibId },
+}
+
+/// Errors constructing [`AluScript`] with [`AluScriptBuilder`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AluScriptBuildError {
+    /// script uses {0} libraries, exceeding the maximum of 255 libraries.
+    TooManyLibs(usize),
+
+    /// script has {0} entry points, exceeding the maximum of 65535 entry
+    /// points.
+    TooManyEntryPoints(usize),
+
+    /// entry point {0} is assigned more than one validator.
+    RepeatedEntryPoint(EntryPoint),
+}
+
+/// Builder assembling [`AluScript`] out of validators for individual entry
+/// points. Libraries shared by several entry points are stored only once.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct AluScriptBuilder {
+    libs: BTreeMap<LibId, Lib>,
+    entry_points: Vec<(EntryPoint, LibSite)>,
+}
+
+impl AluScriptBuilder {
+    /// Constructs builder for an empty script.
+    pub fn new() -> Self { AluScriptBuilder::default() }
+
+    /// Adds validator for the `entry` point, which starts at `offset` within
+    /// the code of the `lib` library.
+    pub fn add_validator(&mut self, entry: EntryPoint, lib: Lib, offset: u16) -> &mut Self {
+        let id = lib.id();
+        self.libs.entry(id).or_insert(lib);
+        self.entry_points.push((entry, LibSite::with(offset, id)));
+        self
+    }
+
+    /// Constructs the script, checking that it doesn't exceed the limits on
+    /// the number of libraries and entry points and that none of the entry
+    /// points has more than one validator.
+    pub fn build(&self) -> Result<AluScript, AluScriptBuildError> {
+        let lib_count = self.libs.len();
+        if lib_count > u8::MAX as usize || lib_count > LIBS_MAX_TOTAL {
+            return Err(AluScriptBuildError::TooManyLibs(lib_count));
+        }
+        let mut entry_points = BTreeMap::new();
+        for (entry, site) in &self.entry_points {
+            if entry_points.insert(*entry, *site).is_some() {
+                return Err(AluScriptBuildError::RepeatedEntryPoint(*entry));
+            }
+        }
+        let entry_count = entry_points.len();
+        Ok(AluScript {
+            libs: Confined::try_from(self.libs.clone()).expect("library count is checked above"),
+            entry_points: Confined::try_from(entry_points)
+                .map_err(|_| AluScriptBuildError::TooManyEntryPoints(entry_count))?,
+        })
+    }
+}
+
 impl Program for AluScript {
     type Isa = RgbIsa;
     type Iter<'a> = btree_map::Values<'a, LibId, Lib> where Self: 'a;
@@ -175,3 +883,526 @@ impl Program for AluScript {
 
     fn entrypoint(&self) -> LibSite { panic!("AluScript doesn't have a single entry point") }
 }
+
+#[cfg(test)]
+mod test {
+    use aluvm::library::LibSeg;
+
+    use super::*;
+
+    #[test]
+    fn libs_by_size() {
+        let small = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let large = Lib::with("ALU", vec![0u8; 64], vec![], LibSeg::default()).unwrap();
+        let script = AluScript {
+            libs: Confined::try_from_iter([
+                (small.id(), small.clone()),
+                (large.id(), large.clone()),
+            ])
+            .unwrap(),
+            entry_points: none!(),
+        };
+        let libs = script.libs_by_size();
+        assert_eq!(libs, vec![
+            (large.id(), large.serialize().len()),
+            (small.id(), small.serialize().len())
+        ]);
+        assert!(AluScript::default().libs_by_size().is_empty());
+    }
+
+    #[test]
+    fn empty_script() {
+        let script = AluScript::default();
+        assert!(script.is_empty());
+        assert!(!script.has_validator_for(&EntryPoint::ValidateGenesis));
+
+        let lib = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let transition = EntryPoint::ValidateTransition(TransitionType::with(1));
+        let script = AluScriptBuilder::new()
+            .add_validator(transition, lib.clone(), 0)
+            .build()
+            .unwrap();
+        assert!(!script.is_empty());
+        assert!(script.has_validator_for(&transition));
+        assert!(!script.has_validator_for(&EntryPoint::ValidateGenesis));
+        assert!(!script.has_validator_for(&EntryPoint::ValidateTransition(TransitionType::with(2))));
+
+        let libs_only = AluScript {
+            libs: Confined::try_from_iter([(lib.id(), lib)]).unwrap(),
+            entry_points: none!(),
+        };
+        assert!(!libs_only.is_empty());
+    }
+
+    #[test]
+    fn entry_points_for_lib() {
+        let lib1 = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let lib2 = Lib::with("ALU", vec![0u8; 8], vec![], LibSeg::default()).unwrap();
+        let unused = Lib::with("ALU", vec![0u8; 16], vec![], LibSeg::default()).unwrap();
+        let transition = EntryPoint::ValidateTransition(TransitionType::with(1));
+        let owned = EntryPoint::ValidateOwnedState(AssignmentType::with(2));
+        let global = EntryPoint::ValidateGlobalState(GlobalStateType::with(3));
+
+        let script = AluScriptBuilder::new()
+            .add_validator(owned, lib1.clone(), 0)
+            .add_validator(EntryPoint::ValidateGenesis, lib1.clone(), 2)
+            .add_validator(transition, lib2.clone(), 0)
+            .add_validator(global, lib2.clone(), 4)
+            .build()
+            .unwrap();
+        assert_eq!(script.entry_points_for_lib(lib1.id()), vec![
+            EntryPoint::ValidateGenesis,
+            owned
+        ]);
+        assert_eq!(script.entry_points_for_lib(lib2.id()), vec![transition, global]);
+        assert!(script.entry_points_for_lib(unused.id()).is_empty());
+    }
+
+    #[test]
+    fn entry_point_kind_subtype() {
+        let entries = [
+            (EntryPoint::ValidateGenesis, EntryPointKind::Genesis, None),
+            (TransitionType::with(1).into(), EntryPointKind::Transition, Some(1)),
+            (ExtensionType::with(2).into(), EntryPointKind::Extension, Some(2)),
+            (GlobalStateType::with(3).into(), EntryPointKind::Global, Some(3)),
+            (AssignmentType::with(u16::MAX).into(), EntryPointKind::Owned, Some(u16::MAX)),
+        ];
+        for (entry, kind, subtype) in entries {
+            assert_eq!(entry.kind(), kind);
+            assert_eq!(entry.subtype(), subtype);
+            assert!(entry.to_string().starts_with(&kind.to_string()));
+        }
+    }
+
+    #[test]
+    fn entry_point_decode() {
+        use strict_encoding::StrictReader;
+
+        let decode = |data: [u8; 3], max: u16| {
+            EntryPoint::strict_decode_bounded(
+                &mut StrictReader::in_memory(data.to_vec(), usize::MAX),
+                max,
+            )
+        };
+        let entries = [
+            EntryPoint::ValidateGenesis,
+            TransitionType::with(1).into(),
+            ExtensionType::with(0x0102).into(),
+            GlobalStateType::with(u16::MAX).into(),
+            AssignmentType::with(7).into(),
+        ];
+        for entry in entries {
+            let data = entry
+                .strict_encode(StrictWriter::in_memory(usize::MAX))
+                .unwrap()
+                .unbox();
+            let decoded =
+                EntryPoint::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap();
+            assert_eq!(decoded, entry);
+        }
+
+        assert_eq!(decode([2, 0x02, 0x01], u16::MAX), Ok(ExtensionType::with(0x0102).into()));
+        assert!(matches!(
+            decode([5, 0, 0], u16::MAX),
+            Err(DecodeError::EnumTagNotKnown(name, 5)) if name == "EntryPoint"
+        ));
+        assert!(matches!(
+            decode([0xFF, 1, 0], u16::MAX),
+            Err(DecodeError::EnumTagNotKnown(_, 0xFF))
+        ));
+        assert!(matches!(decode([0, 1, 0], u16::MAX), Err(DecodeError::DataIntegrityError(_))));
+
+        assert_eq!(decode([4, 10, 0], 10), Ok(AssignmentType::with(10).into()));
+        assert!(matches!(decode([4, 11, 0], 10), Err(DecodeError::DataIntegrityError(_))));
+        assert!(matches!(decode([1, 0, 1], 0xFF), Err(DecodeError::DataIntegrityError(_))));
+        assert_eq!(decode([0, 0, 0], 0), Ok(EntryPoint::ValidateGenesis));
+    }
+
+    #[test]
+    fn coverage_flags() {
+        let lib = Lib::with("ALU", vec![0u8; 8], vec![], LibSeg::default()).unwrap();
+        assert_eq!(AluScript::default().coverage_flags(), CoverageFlags::empty());
+        assert!(AluScript::default().coverage_flags().is_empty());
+
+        let script = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib.clone(), 0)
+            .add_validator(TransitionType::with(1).into(), lib.clone(), 0)
+            .add_validator(TransitionType::with(2).into(), lib.clone(), 2)
+            .build()
+            .unwrap();
+        let flags = script.coverage_flags();
+        assert_eq!(flags, CoverageFlags::GENESIS | CoverageFlags::TRANSITION);
+        assert_eq!(flags.bits(), 0b0000_0011);
+        assert!(flags.contains(CoverageFlags::TRANSITION));
+        assert!(!flags.contains(CoverageFlags::GENESIS | CoverageFlags::EXTENSION));
+        assert_eq!(flags & CoverageFlags::EXTENSION, CoverageFlags::empty());
+
+        let script = AluScriptBuilder::new()
+            .add_validator(ExtensionType::with(1).into(), lib.clone(), 0)
+            .add_validator(AssignmentType::with(2).into(), lib.clone(), 2)
+            .build()
+            .unwrap();
+        assert_eq!(script.coverage_flags(), CoverageFlags::EXTENSION | CoverageFlags::OWNED);
+
+        let script = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib.clone(), 0)
+            .add_validator(TransitionType::with(1).into(), lib.clone(), 0)
+            .add_validator(ExtensionType::with(1).into(), lib.clone(), 0)
+            .add_validator(GlobalStateType::with(1).into(), lib.clone(), 0)
+            .add_validator(AssignmentType::with(1).into(), lib, 0)
+            .build()
+            .unwrap();
+        assert_eq!(script.coverage_flags(), CoverageFlags::all());
+        assert_eq!(CoverageFlags::all().bits(), 0b0001_1111);
+    }
+
+    #[test]
+    fn find_shared_sites() {
+        let lib = Lib::with("ALU", vec![0u8; 8], vec![], LibSeg::default()).unwrap();
+        let transition = EntryPoint::ValidateTransition(TransitionType::with(1));
+        let extension = EntryPoint::ValidateExtension(ExtensionType::with(2));
+        let owned = EntryPoint::ValidateOwnedState(AssignmentType::with(3));
+
+        let script = AluScriptBuilder::new()
+            .add_validator(transition, lib.clone(), 0)
+            .add_validator(extension, lib.clone(), 0)
+            .add_validator(EntryPoint::ValidateGenesis, lib.clone(), 2)
+            .add_validator(owned, lib.clone(), 4)
+            .build()
+            .unwrap();
+        assert_eq!(script.find_shared_sites(), vec![(LibSite::with(0, lib.id()), vec![
+            transition, extension
+        ])]);
+
+        let script = AluScriptBuilder::new()
+            .add_validator(transition, lib.clone(), 0)
+            .add_validator(extension, lib.clone(), 2)
+            .build()
+            .unwrap();
+        assert!(script.find_shared_sites().is_empty());
+    }
+
+    #[test]
+    fn estimate_complexity() {
+        let code = [Instr::<RgbIsa>::Nop, Instr::Nop, Instr::ControlFlow(ControlFlowOp::Succ)];
+        let lib = Lib::assemble(&code).unwrap();
+        let caller = Lib::assemble(&[
+            Instr::<RgbIsa>::ControlFlow(ControlFlowOp::Call(LibSite::with(0, lib.id()))),
+            Instr::ControlFlow(ControlFlowOp::Ret),
+        ])
+        .unwrap();
+        let transition = EntryPoint::ValidateTransition(TransitionType::with(1));
+        let owned = EntryPoint::ValidateOwnedState(AssignmentType::with(2));
+        let script = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib.clone(), 0)
+            .add_validator(transition, lib.clone(), code[0].byte_count())
+            .add_validator(owned, caller, 0)
+            .build()
+            .unwrap();
+
+        let complexity = code.iter().map(Instr::complexity).sum::<u64>();
+        assert_eq!(
+            script.estimate_complexity(EntryPoint::ValidateGenesis),
+            Some(ComplexityEstimate {
+                instructions: 3,
+                complexity,
+                max_stack_depth: Some(0),
+            })
+        );
+        assert_eq!(
+            script.estimate_complexity(transition),
+            Some(ComplexityEstimate {
+                instructions: 2,
+                complexity: complexity - code[0].complexity(),
+                max_stack_depth: Some(0),
+            })
+        );
+        let estimate = script.estimate_complexity(owned).unwrap();
+        assert_eq!(estimate.instructions, 2);
+        assert_eq!(estimate.max_stack_depth, None);
+
+        let global = EntryPoint::ValidateGlobalState(GlobalStateType::with(3));
+        assert_eq!(script.estimate_complexity(global), None);
+        let missing_lib = AluScript {
+            libs: none!(),
+            entry_points: Confined::try_from_iter([(global, LibSite::with(0, lib.id()))]).unwrap(),
+        };
+        assert_eq!(missing_lib.estimate_complexity(global), None);
+    }
+
+    #[test]
+    fn prune_unused_libs() {
+        let callee = Lib::assemble(&[Instr::<RgbIsa>::ControlFlow(ControlFlowOp::Succ)]).unwrap();
+        let caller = Lib::assemble(&[
+            Instr::<RgbIsa>::ControlFlow(ControlFlowOp::Call(LibSite::with(0, callee.id()))),
+            Instr::ControlFlow(ControlFlowOp::Ret),
+        ])
+        .unwrap();
+        let orphan = Lib::with("ALU", vec![0u8; 16], vec![], LibSeg::default()).unwrap();
+        let mut script = AluScript {
+            libs: Confined::try_from_iter([
+                (callee.id(), callee.clone()),
+                (caller.id(), caller.clone()),
+                (orphan.id(), orphan.clone()),
+            ])
+            .unwrap(),
+            entry_points: Confined::try_from_iter([(
+                EntryPoint::ValidateGenesis,
+                LibSite::with(0, caller.id()),
+            )])
+            .unwrap(),
+        };
+
+        assert_eq!(script.prune_unused_libs(), 1);
+        assert!(!script.libs.contains_key(&orphan.id()));
+        assert!(script.libs.contains_key(&caller.id()));
+        assert!(script.libs.contains_key(&callee.id()));
+        assert_eq!(script.prune_unused_libs(), 0);
+
+        script.entry_points = none!();
+        assert_eq!(script.prune_unused_libs(), 2);
+        assert!(script.libs.is_empty());
+    }
+
+    #[test]
+    fn reserialize_libs() {
+        let lib1 = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let lib2 = Lib::with("ALU", vec![0u8; 8], vec![], LibSeg::default()).unwrap();
+        let script = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib1.clone(), 0)
+            .add_validator(TransitionType::with(1).into(), lib2.clone(), 2)
+            .build()
+            .unwrap();
+        let migrated = script.reserialize_libs().unwrap();
+        assert_eq!(migrated, script);
+        assert_eq!(
+            migrated.libs.keys().collect::<Vec<_>>(),
+            script.libs.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(AluScript::default().reserialize_libs().unwrap(), AluScript::default());
+
+        let misplaced = AluScript {
+            libs: Confined::try_from_iter([(lib1.id(), lib2.clone())]).unwrap(),
+            entry_points: none!(),
+        };
+        assert_eq!(
+            misplaced.reserialize_libs(),
+            Err(ReserializeError::IdChanged {
+                expected: lib1.id(),
+                found: lib2.id()
+            })
+        );
+    }
+
+    #[test]
+    fn fingerprint() {
+        let lib1 = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let lib2 = Lib::with("ALU", vec![0u8; 8], vec![], LibSeg::default()).unwrap();
+        let transition = EntryPoint::ValidateTransition(TransitionType::with(1));
+        let owned = EntryPoint::ValidateOwnedState(AssignmentType::with(2));
+
+        let script = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib1.clone(), 0)
+            .add_validator(transition, lib2.clone(), 2)
+            .add_validator(owned, lib1.clone(), 1)
+            .build()
+            .unwrap();
+        let reordered = AluScriptBuilder::new()
+            .add_validator(owned, lib1.clone(), 1)
+            .add_validator(transition, lib2.clone(), 2)
+            .add_validator(EntryPoint::ValidateGenesis, lib1.clone(), 0)
+            .build()
+            .unwrap();
+        assert_eq!(script.fingerprint(), reordered.fingerprint());
+        assert_ne!(script.fingerprint(), AluScript::default().fingerprint());
+
+        let moved = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib1.clone(), 0)
+            .add_validator(transition, lib2, 2)
+            .add_validator(owned, lib1, 2)
+            .build()
+            .unwrap();
+        assert_ne!(script.fingerprint(), moved.fingerprint());
+    }
+
+    #[test]
+    fn script_diff() {
+        let lib1 = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let lib2 = Lib::with("ALU", vec![0u8; 8], vec![], LibSeg::default()).unwrap();
+        let lib3 = Lib::with("ALU", vec![0u8; 16], vec![], LibSeg::default()).unwrap();
+        let transition = EntryPoint::ValidateTransition(TransitionType::with(1));
+        let owned = EntryPoint::ValidateOwnedState(AssignmentType::with(2));
+        let global = EntryPoint::ValidateGlobalState(GlobalStateType::with(3));
+
+        let old = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib1.clone(), 0)
+            .add_validator(transition, lib1.clone(), 2)
+            .add_validator(owned, lib2.clone(), 0)
+            .build()
+            .unwrap();
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(old.diff(&old).to_string(), "no changes\n");
+
+        let new = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib1.clone(), 0)
+            .add_validator(transition, lib1.clone(), 1)
+            .add_validator(global, lib3.clone(), 0)
+            .build()
+            .unwrap();
+        let diff = old.diff(&new);
+        assert_eq!(diff.libs_added, bset![lib3.id()]);
+        assert_eq!(diff.libs_removed, bset![lib2.id()]);
+        assert_eq!(diff.libs_changed, bset![lib1.id()]);
+        assert_eq!(diff.entry_points_changed, bmap! {
+            transition => (Some(LibSite::with(2, lib1.id())), Some(LibSite::with(1, lib1.id()))),
+            owned => (Some(LibSite::with(0, lib2.id())), None),
+            global => (None, Some(LibSite::with(0, lib3.id())))
+        });
+        assert_eq!(
+            diff.to_string(),
+            format!(
+                "+ lib {lib3}\n- lib {lib2}\n~ lib {lib1}\n~ transition:1: 2 @ {lib1} -> 1 @ \
+                 {lib1}\n+ global:3 -> 0 @ {lib3}\n- owned:2 -> 0 @ {lib2}\n",
+                lib1 = lib1.id(),
+                lib2 = lib2.id(),
+                lib3 = lib3.id()
+            )
+        );
+        assert_eq!(new.diff(&old).libs_added, bset![lib2.id()]);
+    }
+
+    #[test]
+    fn builder_shared_lib() {
+        let lib = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let other = Lib::with("ALU", vec![0u8; 8], vec![], LibSeg::default()).unwrap();
+        let transition = EntryPoint::ValidateTransition(TransitionType::with(1));
+        let owned = EntryPoint::ValidateOwnedState(AssignmentType::with(2));
+
+        let script = AluScriptBuilder::new()
+            .add_validator(EntryPoint::ValidateGenesis, lib.clone(), 0)
+            .add_validator(transition, lib.clone(), 2)
+            .build()
+            .unwrap();
+        assert_eq!(script.libs.len(), 1);
+        assert_eq!(script.entry_points.len(), 2);
+        assert_eq!(script.entry_points.get(&transition), Some(&LibSite::with(2, lib.id())));
+
+        let mut builder = AluScriptBuilder::new();
+        builder
+            .add_validator(transition, lib.clone(), 0)
+            .add_validator(owned, other.clone(), 0);
+        assert_eq!(builder.build().unwrap().libs.len(), 2);
+        builder.add_validator(owned, lib, 1);
+        assert_eq!(builder.build(), Err(AluScriptBuildError::RepeatedEntryPoint(owned)));
+    }
+
+    #[test]
+    fn entry_point_str_roundtrip() {
+        for entry in [
+            EntryPoint::ValidateGenesis,
+            EntryPoint::ValidateTransition(TransitionType::with(1)),
+            EntryPoint::ValidateExtension(ExtensionType::with(2)),
+            EntryPoint::ValidateGlobalState(GlobalStateType::with(3)),
+            EntryPoint::ValidateOwnedState(AssignmentType::with(u16::MAX)),
+        ] {
+            assert_eq!(EntryPoint::from_str(&entry.to_string()), Ok(entry));
+        }
+        assert_eq!(EntryPoint::ValidateGenesis.to_string(), "genesis");
+        assert_eq!(EntryPoint::ValidateOwnedState(AssignmentType::with(4)).to_string(), "owned:4");
+        assert_eq!(
+            EntryPoint::from_str("owned"),
+            Err(EntryPointParseError::NoSubtype(s!("owned")))
+        );
+        assert_eq!(
+            EntryPoint::from_str("state:1"),
+            Err(EntryPointParseError::UnknownKind(s!("state")))
+        );
+        assert!(matches!(
+            EntryPoint::from_str("global:x"),
+            Err(EntryPointParseError::InvalidSubtype(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_repr_roundtrip() {
+        use baid58::ToBaid58;
+        use base64::Engine;
+
+        let lib = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let script = AluScript {
+            libs: Confined::try_from_iter([(lib.id(), lib.clone())]).unwrap(),
+            entry_points: Confined::try_from_iter([(
+                EntryPoint::ValidateTransition(TransitionType::with(1)),
+                LibSite::with(0, lib.id()),
+            )])
+            .unwrap(),
+        };
+        let repr = AluScriptSerde::from(script.clone());
+        assert_eq!(
+            repr.libs.get(&lib.id().to_baid58().to_string()),
+            Some(&base64::engine::general_purpose::STANDARD.encode(lib.serialize()))
+        );
+        assert_eq!(repr.entry_points.get("transition:1"), Some(&LibSite::with(0, lib.id())));
+        assert_eq!(AluScript::try_from(repr), Ok(script));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_golden() {
+        let lib = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let script = AluScript {
+            libs: Confined::try_from_iter([(lib.id(), lib.clone())]).unwrap(),
+            entry_points: Confined::try_from_iter([
+                (EntryPoint::ValidateGenesis, LibSite::with(0, lib.id())),
+                (
+                    EntryPoint::ValidateOwnedState(AssignmentType::with(4)),
+                    LibSite::with(2, lib.id()),
+                ),
+            ])
+            .unwrap(),
+        };
+        let json = serde_json::to_string(&script).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"libs":{"2arKgVXHm9R5SFSQ4cAsmPes4Qa9AGcNzT4Z9dVZLuSx":"A0FMVQQAAAAAAAAAAA=="},"#,
+                r#""entryPoints":{"#,
+                r#""genesis":{"lib":"17879f4e07c999b438da74d6135b8acfc5d01d0b41dd6a996e936334865504a9","pos":0},"#,
+                r#""owned:4":{"lib":"17879f4e07c999b438da74d6135b8acfc5d01d0b41dd6a996e936334865504a9","pos":2}}}"#
+            )
+        );
+        assert_eq!(serde_json::from_str::<AluScript>(&json).unwrap(), script);
+
+        let bad_id = json.replace("2arKgVXHm9R5SFSQ4cAsmPes4Qa9AGcNzT4Z9dVZLuSx", "not-a-lib-id");
+        assert!(serde_json::from_str::<AluScript>(&bad_id).is_err());
+        let bad_entry = json.replace("owned:4", "owned:x");
+        assert!(serde_json::from_str::<AluScript>(&bad_entry).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn lib_id_mismatch() {
+        use baid58::ToBaid58;
+        use strict_encoding::{StrictReader, StrictWriter};
+
+        let lib = Lib::with("ALU", vec![0u8; 4], vec![], LibSeg::default()).unwrap();
+        let other = Lib::with("ALU", vec![1u8; 4], vec![], LibSeg::default()).unwrap();
+        let script = AluScript {
+            libs: Confined::try_from_iter([(other.id(), lib.clone())]).unwrap(),
+            entry_points: none!(),
+        };
+
+        let data = script
+            .strict_encode(StrictWriter::in_memory(usize::MAX))
+            .unwrap()
+            .unbox();
+        let decoded =
+            AluScript::strict_decode(&mut StrictReader::in_memory(data, usize::MAX)).unwrap();
+        assert_eq!(decoded, script);
+
+        let repr = AluScriptSerde::from(script);
+        assert_eq!(repr.libs.keys().next(), Some(&other.id().to_baid58().to_string()));
+        assert_eq!(AluScript::try_from(repr), Err(AluScriptSerdeError::LibIdMismatch(other.id())));
+    }
+}